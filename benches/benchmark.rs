@@ -4,7 +4,7 @@ extern crate mime_guess;
 
 use self::criterion::Criterion;
 
-use mime_guess::from_ext;
+use mime_guess::{from_ext, from_path, MimeGuess};
 
 include!("../src/mime_types.rs");
 
@@ -19,7 +19,7 @@ fn bench_mime_str(c: &mut Criterion) {
 
 fn bench_mime_str_uppercase(c: &mut Criterion) {
     c.bench_function("from_ext uppercased", |b| {
-        let uppercased = MIME_TYPES.into_iter().map(|(s, _)| s.to_uppercase());
+        let uppercased = MIME_TYPES.iter().map(|(s, _)| s.to_uppercase());
 
         for mime_ext in uppercased {
             b.iter(|| from_ext(&mime_ext).first_raw());
@@ -27,5 +27,33 @@ fn bench_mime_str_uppercase(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_mime_str, bench_mime_str_uppercase);
+fn bench_from_paths(c: &mut Criterion) {
+    let paths: Vec<_> = MIME_TYPES
+        .iter()
+        .map(|(ext, _)| format!("some/dir/file.{}", ext))
+        .collect();
+
+    c.bench_function("from_path repeated", |b| {
+        b.iter(|| {
+            for path in &paths {
+                from_path(path).first_raw();
+            }
+        })
+    });
+
+    c.bench_function("from_paths", |b| {
+        b.iter(|| {
+            for (_, guess) in MimeGuess::from_paths(&paths) {
+                guess.first_raw();
+            }
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_mime_str,
+    bench_mime_str_uppercase,
+    bench_from_paths
+);
 criterion_main!(benches);
@@ -17,6 +17,10 @@ use mime_types::MIME_TYPES;
 #[path = "src/mime_types.rs"]
 mod mime_types;
 
+#[cfg(feature = "rev-mappings")]
+#[path = "src/preferred_exts.rs"]
+mod preferred_exts;
+
 #[cfg(feature = "phf")]
 const PHF_PATH: &str = "::impl_::phf";
 
@@ -35,6 +39,9 @@ fn main() {
 
     #[cfg(feature = "rev-mappings")]
     build_rev_map(&mut outfile);
+
+    #[cfg(feature = "capi")]
+    build_nul_terminated(&mut outfile);
 }
 
 // Build forward mappings (ext -> mime type)
@@ -117,7 +124,7 @@ fn build_rev_map<W: Write>(out: &mut W) {
         rev_map.build()
     ).unwrap();
 
-    writeln!(out, "const EXTS: &'static [&'static str] = &{:?};", exts).unwrap();
+    writeln!(out, "const EXTS: &[&str] = &{:?};", exts).unwrap();
 }
 
 #[cfg(all(not(feature = "phf"), feature = "rev-mappings"))]
@@ -136,7 +143,7 @@ fn build_rev_map<W: Write>(out: &mut W) {
 
     let dyn_map = get_rev_mappings();
 
-    write!(out, "static REV_MAPPINGS: &'static [(UniCase<&'static str>, TopLevelExts)] = &[").unwrap();
+    write!(out, "static REV_MAPPINGS: &[(UniCase<&'static str>, TopLevelExts)] = &[").unwrap();
 
     let mut exts = Vec::new();
 
@@ -168,7 +175,22 @@ fn build_rev_map<W: Write>(out: &mut W) {
 
     writeln!(out, "];").unwrap();
 
-    writeln!(out, "const EXTS: &'static [&'static str] = &{:?};", exts).unwrap();
+    writeln!(out, "const EXTS: &[&str] = &{:?};", exts).unwrap();
+}
+
+// Build NUL-terminated copies of every media type, sorted, for the C ABI to return
+#[cfg(feature = "capi")]
+fn build_nul_terminated<W: Write>(out: &mut W) {
+    let mut mimes: Vec<&str> = MIME_TYPES
+        .iter()
+        .flat_map(|&(_, types)| types.iter().cloned())
+        .collect();
+    mimes.sort();
+    mimes.dedup();
+
+    let mimes: Vec<String> = mimes.iter().map(|mime| format!("{}\0", mime)).collect();
+
+    writeln!(out, "static MIME_TYPES_NUL: &[&str] = &{:?};", mimes).unwrap();
 }
 
 #[cfg(feature = "rev-mappings")]
@@ -187,6 +209,21 @@ fn get_rev_mappings(
                 .push(key);
         }
     }
+
+    // Extensions are collected in table (alphabetical) order; move the preferred extension
+    // for each type to the front so it is listed first, e.g. `jpg` for `image/jpeg`
+    for (top, subs) in &mut dyn_map {
+        for (sub, exts) in subs {
+            let mime = format!("{}/{}", top, sub);
+            let preferred = preferred_exts::get_preferred(&mime)
+                .and_then(|pref| exts.iter().position(|ext| ext.eq_ignore_ascii_case(pref)));
+
+            if let Some(idx) = preferred {
+                exts[..=idx].rotate_right(1);
+            }
+        }
+    }
+
     dyn_map
 }
 
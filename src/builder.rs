@@ -0,0 +1,107 @@
+#[cfg(feature = "path")]
+use std::ffi::OsStr;
+#[cfg(feature = "path")]
+use std::path::Path;
+
+#[cfg(feature = "path")]
+use {path_extension, trailing_extensions};
+use {strip_leading_dot, MimeGuess};
+
+/// A reusable configuration of how to guess MIME types from extensions and paths.
+///
+/// Each option corresponds to one of the specialized constructors of
+/// [`MimeGuess`](struct.MimeGuess.html), but they can be combined freely. All options are
+/// off by default, in which case [`guess_ext()`](#method.guess_ext) and
+/// [`guess_path()`](#method.guess_path) are the same as
+/// [`MimeGuess::from_ext()`](struct.MimeGuess.html#method.from_ext) and
+/// [`MimeGuess::from_path()`](struct.MimeGuess.html#method.from_path).
+///
+/// ```rust
+/// use mime_guess::MimeGuessBuilder;
+///
+/// # #[cfg(feature = "path")] {
+/// let builder = MimeGuessBuilder::new()
+///     .strip_leading_dot(true)
+///     .compound_extensions(true);
+///
+/// assert_eq!(builder.guess_ext(".gif").first_raw(), Some("image/gif"));
+/// assert_eq!(
+///     builder.guess_path("backup.tar.gz").first_raw(),
+///     Some("application/x-compressed-tar")
+/// );
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MimeGuessBuilder {
+    case_sensitive: bool,
+    strip_leading_dot: bool,
+    #[cfg(feature = "path")]
+    compound_extensions: bool,
+}
+
+impl MimeGuessBuilder {
+    /// Create a builder with all options off.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match extensions with exactly the same case as stored in the crate's table, as for
+    /// [`MimeGuess::from_ext_case_sensitive()`](struct.MimeGuess.html#method.from_ext_case_sensitive).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Allow extensions passed to [`guess_ext()`](#method.guess_ext) to be written with a
+    /// leading dot or as a glob, as for
+    /// [`MimeGuess::from_ext_lenient()`](struct.MimeGuess.html#method.from_ext_lenient).
+    /// Has no effect on [`guess_path()`](#method.guess_path).
+    pub fn strip_leading_dot(mut self, strip_leading_dot: bool) -> Self {
+        self.strip_leading_dot = strip_leading_dot;
+        self
+    }
+
+    /// Recognize compound extensions such as `tar.gz` in [`guess_path()`](#method.guess_path),
+    /// as for [`MimeGuess::from_path_compound()`](struct.MimeGuess.html#method.from_path_compound).
+    #[cfg(feature = "path")]
+    pub fn compound_extensions(mut self, compound_extensions: bool) -> Self {
+        self.compound_extensions = compound_extensions;
+        self
+    }
+
+    /// Guess the MIME type of a file with the given extension.
+    pub fn guess_ext(&self, ext: &str) -> MimeGuess {
+        let ext = if self.strip_leading_dot {
+            strip_leading_dot(ext)
+        } else {
+            ext
+        };
+
+        self.lookup(ext)
+    }
+
+    /// Guess the MIME type of `path` by its extension. **No disk access is performed.**
+    #[cfg(feature = "path")]
+    pub fn guess_path<P: AsRef<Path>>(&self, path: P) -> MimeGuess {
+        let path = path.as_ref();
+
+        if self.compound_extensions {
+            trailing_extensions(path)
+                .map(|ext| self.lookup(ext))
+                .find(|guess| !guess.is_empty())
+                .unwrap_or_else(|| MimeGuess::from_static(&[]))
+        } else {
+            path_extension(path)
+                .and_then(OsStr::to_str)
+                .map_or(MimeGuess::from_static(&[]), |ext| self.lookup(ext))
+        }
+    }
+
+    fn lookup(&self, ext: &str) -> MimeGuess {
+        if self.case_sensitive {
+            MimeGuess::from_ext_case_sensitive(ext)
+        } else {
+            MimeGuess::from_ext(ext)
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! A C ABI for guessing MIME types by extension.
+//!
+//! Only available with the `capi` feature. The functions here are safe to call from any
+//! thread: the crate's table is immutable and no state is shared between calls. Nothing is
+//! allocated, so there is nothing to free; returned strings are static and must not be freed
+//! or modified.
+//!
+//! ```c
+//! // wrap in `extern "C" { ... }` when compiling as C++
+//! const char *mime_guess_from_ext(const char *ext);
+//!
+//! const char *mime = mime_guess_from_ext("gif");
+//! if (mime) {
+//!     puts(mime); // image/gif
+//! }
+//! ```
+//!
+//! ### Linking
+//! Build a shared or static library to link against with `cargo rustc`, which needs Cargo
+//! 1.64 or later for `--crate-type`:
+//!
+//! ```sh
+//! cargo rustc --release --lib --features capi --crate-type cdylib      # libmime_guess.so
+//! cargo rustc --release --lib --features capi --crate-type staticlib   # libmime_guess.a
+//! ```
+//!
+//! The crate itself only declares the default `lib` type, so depending on it from Rust does not
+//! build these.
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::ptr;
+
+use {impl_, MimeGuess};
+
+/// Get the first media type guessed for the NUL-terminated extension `ext`, as a static
+/// NUL-terminated string which must not be freed.
+///
+/// Returns `NULL` if `ext` is `NULL` or not valid UTF-8, or has no known MIME type mapping.
+///
+/// # Safety
+/// `ext` must be `NULL` or point to a NUL-terminated string which stays valid for the duration
+/// of the call.
+#[no_mangle]
+pub unsafe extern "C" fn mime_guess_from_ext(ext: *const c_char) -> *const c_char {
+    if ext.is_null() {
+        return ptr::null();
+    }
+
+    CStr::from_ptr(ext)
+        .to_str()
+        .ok()
+        .and_then(|ext| MimeGuess::from_ext(ext).first_raw())
+        .and_then(impl_::get_nul_terminated)
+        .map_or(ptr::null(), |mime| mime.as_ptr() as *const c_char)
+}
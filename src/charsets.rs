@@ -0,0 +1,36 @@
+use unicase::UniCase;
+
+/// A mapping of media types to the charset which content of that type should be labelled with
+/// when its actual charset is not known.
+///
+/// Required to be sorted lexicographically by media type. Types not listed here have no
+/// default charset (e.g. binary formats, or formats which declare their encoding internally).
+///
+/// `utf-8` is used wherever the type allows it; types which are restricted to ASCII by
+/// definition, such as ASCII-armored OpenPGP data ([RFC 3156]), use `us-ascii`.
+///
+/// [RFC 3156]: https://tools.ietf.org/html/rfc3156
+pub static DEFAULT_CHARSETS: &[(&str, &str)] = &[
+    ("application/javascript", "utf-8"),
+    ("application/json", "utf-8"),
+    ("application/pgp-signature", "us-ascii"),
+    ("application/xml", "utf-8"),
+    ("image/svg+xml", "utf-8"),
+    ("text/calendar", "utf-8"),
+    ("text/css", "utf-8"),
+    ("text/csv", "utf-8"),
+    ("text/html", "utf-8"),
+    ("text/javascript", "utf-8"),
+    ("text/markdown", "utf-8"),
+    ("text/plain", "utf-8"),
+    ("text/vcard", "utf-8"),
+    ("text/xml", "utf-8"),
+];
+
+/// Get the default charset for the `<type>/<subtype>` essence `mime`. Case-insensitive.
+pub fn get_default_charset(mime: &str) -> Option<&'static str> {
+    DEFAULT_CHARSETS
+        .binary_search_by_key(&UniCase::new(mime), |&(mime, _)| UniCase::new(mime))
+        .ok()
+        .map(|i| DEFAULT_CHARSETS[i].1)
+}
@@ -0,0 +1,87 @@
+use unicase::UniCase;
+
+use is_textual;
+
+/// A mapping of media types to whether content of that type benefits from general-purpose
+/// compression (e.g. `Content-Encoding: gzip`).
+///
+/// Required to be sorted lexicographically by media type. This lists the exceptions to the
+/// rules in `is_compressible()`: uncompressed binary formats, which compress well even though
+/// they are not textual, and archive formats whose type does not otherwise mark them as
+/// compressed.
+///
+/// The flags are taken from the `compressible` field of [mime-db] 1.52.0. Entries marked
+/// "curated" have no such field there and are judged by their format instead.
+///
+/// [mime-db]: https://github.com/jshttp/mime-db/blob/v1.52.0/db.json
+pub static COMPRESSIBLE: &[(&str, bool)] = &[
+    // curated: the same sfnt container as `font/ttf`
+    ("application/font-sfnt", true),
+    ("application/font-woff", false),
+    ("application/gzip", false),
+    ("application/java-archive", false),
+    ("application/vnd.android.package-archive", false),
+    ("application/vnd.ms-fontobject", true),
+    ("application/wasm", true),
+    ("application/x-7z-compressed", false),
+    ("application/x-bzip2", false),
+    ("application/x-font-ttf", true),
+    // curated: legacy alias of `application/gzip`
+    ("application/x-gzip", false),
+    ("application/x-rar-compressed", false),
+    ("application/x-tar", true),
+    // curated: XZ-compressed data
+    ("application/x-xz", false),
+    ("application/zip", false),
+    // curated: Zstandard-compressed data (RFC 8878)
+    ("application/zstd", false),
+    ("font/otf", true),
+    ("font/ttf", true),
+    // curated: WOFF and WOFF2 are compressed by definition
+    ("font/woff", false),
+    ("font/woff2", false),
+    ("image/bmp", true),
+    ("image/vnd.microsoft.icon", true),
+    ("image/x-icon", true),
+];
+
+/// Prefixes of subtypes for ZIP-based office document formats.
+static ZIP_DOCUMENT_PREFIXES: &[&str] = &[
+    "vnd.oasis.opendocument.",
+    "vnd.openxmlformats-officedocument.",
+];
+
+/// `true` if content of the `<type>/<subtype>` essence `mime` benefits from compression.
+/// Case-insensitive.
+///
+/// In order:
+///
+/// * types in `COMPRESSIBLE` take the value listed there;
+/// * types with the structured syntax suffix `+zip` and ZIP-based office documents are not
+///   compressible;
+/// * textual types (see `MimeGuess::is_text()`) are compressible;
+/// * anything else is assumed to be compressed already, including all other `image/*`,
+///   `audio/*` and `video/*` types.
+pub fn is_compressible(mime: &str) -> bool {
+    if let Ok(i) =
+        COMPRESSIBLE.binary_search_by_key(&UniCase::new(mime), |&(mime, _)| UniCase::new(mime))
+    {
+        return COMPRESSIBLE[i].1;
+    }
+
+    let sub = mime.find('/').map_or("", |idx| &mime[idx + 1..]).as_bytes();
+
+    if ends_with_ignore_case(sub, b"+zip")
+        || ZIP_DOCUMENT_PREFIXES.iter().any(|prefix| {
+            sub.len() >= prefix.len() && sub[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+        })
+    {
+        return false;
+    }
+
+    is_textual(mime)
+}
+
+fn ends_with_ignore_case(s: &[u8], suffix: &[u8]) -> bool {
+    s.len() >= suffix.len() && s[s.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+}
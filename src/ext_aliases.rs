@@ -0,0 +1,38 @@
+use unicase::UniCase;
+
+/// A mapping of alternative spellings of file extensions to the canonical extension they are
+/// equivalent to, e.g. `jpeg` to `jpg`.
+///
+/// Required to be sorted lexicographically by alias. Both the alias and the canonical
+/// extension must be in `MIME_TYPES` with the same MIME types.
+///
+/// Unless noted otherwise, each alias is listed together with its canonical extension for a
+/// single type in Apache's [`mime.types`] and in [mime-db].
+///
+/// [`mime.types`]: https://svn.apache.org/repos/asf/httpd/httpd/trunk/docs/conf/mime.types
+/// [mime-db]: https://github.com/jshttp/mime-db
+pub static EXT_ALIASES: &[(&str, &str)] = &[
+    ("aif", "aiff"),
+    ("htm", "html"),
+    // listed with `jpg` for `image/jpeg` by freedesktop.org's shared-mime-info instead
+    ("jfif", "jpg"),
+    ("jpe", "jpg"),
+    ("jpeg", "jpg"),
+    ("midi", "mid"),
+    ("mpe", "mpg"),
+    ("mpeg", "mpg"),
+    ("qt", "mov"),
+    ("text", "txt"),
+    ("tif", "tiff"),
+    ("xht", "xhtml"),
+    // RFC 9512 (`application/yaml`) lists both; yaml.org recommends `yaml`
+    ("yml", "yaml"),
+];
+
+/// Get the canonical extension for `alias`, or `None` if it is not an alias. Case-insensitive.
+pub fn get_canonical(alias: &str) -> Option<&'static str> {
+    EXT_ALIASES
+        .binary_search_by_key(&UniCase::new(alias), |&(alias, _)| UniCase::new(alias))
+        .ok()
+        .map(|i| EXT_ALIASES[i].1)
+}
@@ -17,6 +17,25 @@ pub fn get_mime_types(ext: &str) -> Option<&'static [&'static str]> {
     map_lookup(MIME_TYPES, &ext)
 }
 
+pub fn get_entry(ext: &str) -> Option<(&'static str, &'static [&'static str])> {
+    MIME_TYPES
+        .binary_search_by_key(&UniCase::new(ext), |&(k, _)| UniCase::new(k))
+        .ok()
+        .map(|i| MIME_TYPES[i])
+}
+
+pub fn entries() -> impl Iterator<Item = (&'static str, &'static [&'static str])> {
+    MIME_TYPES.iter().cloned()
+}
+
+#[cfg(feature = "capi")]
+pub fn get_nul_terminated(mime: &str) -> Option<&'static str> {
+    MIME_TYPES_NUL
+        .binary_search_by_key(&mime, |s| &s[..s.len() - 1])
+        .ok()
+        .map(|i| MIME_TYPES_NUL[i])
+}
+
 #[cfg(feature = "rev-mappings")]
 pub fn get_extensions(toplevel: &str, sublevel: &str) -> Option<&'static [&'static str]> {
     if toplevel == "*" {
@@ -29,7 +48,7 @@ pub fn get_extensions(toplevel: &str, sublevel: &str) -> Option<&'static [&'stat
         return Some(&EXTS[top.start..top.end]);
     }
 
-    let sub = map_lookup(&top.subs, sublevel)?;
+    let sub = map_lookup(top.subs, sublevel)?;
     Some(&EXTS[sub.0..sub.1])
 }
 
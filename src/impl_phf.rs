@@ -15,6 +15,24 @@ pub fn get_mime_types(ext: &str) -> Option<&'static [&'static str]> {
     map_lookup(&MIME_TYPES, ext).cloned()
 }
 
+pub fn get_entry(ext: &str) -> Option<(&'static str, &'static [&'static str])> {
+    MIME_TYPES
+        .get_entry(&UniCase::new(ext))
+        .map(|(k, v)| ((*k).into_inner(), *v))
+}
+
+pub fn entries() -> impl Iterator<Item = (&'static str, &'static [&'static str])> {
+    MIME_TYPES.entries().map(|(k, v)| ((*k).into_inner(), *v))
+}
+
+#[cfg(feature = "capi")]
+pub fn get_nul_terminated(mime: &str) -> Option<&'static str> {
+    MIME_TYPES_NUL
+        .binary_search_by_key(&mime, |s| &s[..s.len() - 1])
+        .ok()
+        .map(|i| MIME_TYPES_NUL[i])
+}
+
 pub fn get_extensions(toplevel: &str, sublevel: &str) -> Option<&'static [&'static str]> {
     if toplevel == "*" {
         return Some(EXTS);
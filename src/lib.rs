@@ -4,10 +4,11 @@
 //!
 //! ```
 //! # extern crate mime;
+//! # #[cfg(feature = "path")] {
 //! // the file doesn't have to exist, it just looks at the path
 //! let guess = mime_guess::from_path("some_file.gif");
 //! assert_eq!(guess.first(), Some(mime::IMAGE_GIF));
-//!
+//! # }
 //! ```
 //!
 //! #### Note: MIME Types Returned Are Not Stable/Guaranteed
@@ -19,15 +20,61 @@
 //! type. The file that may or may not reside at that path may or may not be a valid file of the
 //! returned MIME type.  Be wary of unsafe or un-validated assumptions about file structure or
 //! length.
+//!
+//! #### Note: Paths and URLs
+//! Guessing from paths and URLs, i.e. [`from_path()`](fn.from_path.html), the
+//! `MimeGuess::from_path*()` and `MimeGuess::from_url()` constructors and everything built on
+//! them, requires the `path` feature. It is enabled by default; turn it off where only
+//! extensions are needed, e.g. on `wasm32-unknown-unknown`.
+//!
+//! #### Note: No IANA Registration Data
+//! The table records only which media types an extension maps to. It has no IANA registration
+//! metadata, such as template or registration names, because its sources (e.g. [mime-db]) do not
+//! record it either. Media types therefore cannot be looked up by their registration name. Use
+//! the media-type string itself, e.g. with
+//! [`get_mime_extensions_str()`](fn.get_mime_extensions_str.html), instead.
+//!
+//! [mime-db]: https://github.com/jshttp/mime-db
 pub extern crate mime;
+#[cfg(feature = "serde")]
+extern crate serde;
 extern crate unicase;
 
+#[cfg(test)]
+extern crate serde_json;
+
+pub use builder::MimeGuessBuilder;
 pub use mime::Mime;
+pub use owned::OwnedMimeGuess;
+pub use registry::{MimeRegistry, RegistryGuess, RegistryIterRaw};
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::error;
 use std::ffi::OsStr;
+use std::fmt;
+#[cfg(feature = "path")]
+use std::fs;
+use std::io::{self, Write};
 use std::iter::FusedIterator;
-use std::path::Path;
-use std::{iter, slice};
+#[cfg(feature = "path")]
+use std::path::{self, Path};
+use std::{iter, mem, ops, slice, str, vec};
+
+mod builder;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod charsets;
+mod compressible;
+mod ext_aliases;
+mod mime_aliases;
+mod owned;
+#[cfg(feature = "rev-mappings")]
+mod preferred_exts;
+mod registry;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "sniff")]
+pub mod sniff;
 
 #[cfg(feature = "phf")]
 #[path = "impl_phf.rs"]
@@ -37,6 +84,15 @@ mod impl_;
 #[path = "impl_bin_search.rs"]
 mod impl_;
 
+/// The maximum length in bytes of an extension which can have a known MIME type mapping.
+///
+/// No known extension is anywhere near this long, so [`MimeGuess::from_ext()`] returns an
+/// empty guess for longer inputs without searching the table, bounding the cost of lookups
+/// on untrusted input such as uploaded file names.
+///
+/// [`MimeGuess::from_ext()`]: struct.MimeGuess.html#method.from_ext
+pub const MAX_EXTENSION_LEN: usize = 32;
+
 /// A "guess" of the MIME/Media Type(s) of an extension or path as one or more
 /// [`Mime`](struct.Mime.html) instances.
 ///
@@ -44,28 +100,188 @@ mod impl_;
 /// A given file format may have one or more applicable Media Types; in this case
 /// the first Media Type returned is whatever is declared in the latest IETF RFC for the
 /// presumed file format or the one that explicitly supercedes all others.
-/// Ordering of additional Media Types is arbitrary.
+/// Ordering of additional Media Types has no particular significance, but it is deterministic:
+/// Media Types are always returned in the order they are listed in the crate's table for the
+/// extension, regardless of build environment or lookup implementation.
 ///
 /// ### Note: Values Not Stable
 /// The exact Media Types returned in any given guess are not considered to be stable and are often
 /// updated in patch releases in order to reflect the most up-to-date information possible.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///
+/// ### Note: Serialization
+/// With the `serde` feature, this implements `Serialize` as a sequence of its media-type
+/// strings, but not `Deserialize` as it borrows the crate's static table; deserialize into
+/// [`OwnedMimeGuess`](struct.OwnedMimeGuess.html) instead.
+///
+/// ### Note: Display
+/// `Display` writes the Media Types separated by `, `, e.g. `image/jpeg, image/pjpeg`.
+/// As parsing cannot produce a guess borrowing the static table, that form is parsed
+/// by [`OwnedMimeGuess`](struct.OwnedMimeGuess.html)'s `FromStr` implementation instead.
+///
+/// ### Note: Comparison and Hashing
+/// Guesses compare and hash by the media-type strings they contain, not by address.
+/// `Ord` compares those strings lexicographically, element by element, as for slices.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 // FIXME: change repr when `mime` gains macro/const fn constructor
 pub struct MimeGuess(&'static [&'static str]);
 
 impl MimeGuess {
+    /// Create a guess from a fixed list of media types, e.g. for a custom extension.
+    ///
+    /// The strings are **not** validated here; they are only parsed when a `Mime` is requested,
+    /// e.g. by [`first()`](#method.first) or [`iter()`](#method.iter), which will panic if
+    /// one is not a valid media type.
+    ///
+    /// ```rust
+    /// use mime_guess::MimeGuess;
+    ///
+    /// const QML: MimeGuess = MimeGuess::from_static(&["text/x-qml"]);
+    ///
+    /// assert_eq!(QML.first_raw(), Some("text/x-qml"));
+    /// ```
+    pub const fn from_static(mimes: &'static [&'static str]) -> MimeGuess {
+        MimeGuess(mimes)
+    }
+
     /// Guess the MIME type of a file (real or otherwise) with the given extension.
     ///
     /// The search is case-insensitive.
     ///
     /// If `ext` is empty or has no (currently) known MIME type mapping, then an empty guess is
-    /// returned.
+    /// returned. This includes any `ext` longer than [`MAX_EXTENSION_LEN`] bytes, for which
+    /// no lookup is attempted at all.
+    ///
+    /// [`MAX_EXTENSION_LEN`]: constant.MAX_EXTENSION_LEN.html
     pub fn from_ext(ext: &str) -> MimeGuess {
-        if ext.is_empty() {
+        if !is_searchable_ext(ext) {
+            return MimeGuess(&[]);
+        }
+
+        impl_::get_mime_types(ext).map_or(MimeGuess(&[]), MimeGuess)
+    }
+
+    /// Guess the MIME type of a file with the given extension, as returned by
+    /// `Path::extension()`.
+    ///
+    /// If `ext` is not valid UTF-8, an empty guess is returned, as for
+    /// [`from_path()`](#method.from_path). Otherwise this is the same as
+    /// [`from_ext()`](#method.from_ext).
+    pub fn from_os_ext<S: AsRef<OsStr>>(ext: S) -> MimeGuess {
+        ext.as_ref().to_str().map_or(MimeGuess(&[]), Self::from_ext)
+    }
+
+    /// Guess the MIME type of a file with the given extension, allowing it to be written with a
+    /// leading dot (`.gif`) or as a glob (`*.gif`).
+    ///
+    /// A single leading `*.` or `.` is stripped before lookup, and nothing else, so internal
+    /// dots (as in `tar.gz`) are left alone. Otherwise this is the same as
+    /// [`from_ext()`](#method.from_ext); in particular, if nothing is left after stripping
+    /// (e.g. for `.`), an empty guess is returned.
+    pub fn from_ext_lenient(ext: &str) -> MimeGuess {
+        Self::from_ext(strip_leading_dot(ext))
+    }
+
+    /// Guess the MIME type of a file with the given extension, only matching extensions with
+    /// exactly the same case as stored in the crate's table (lowercase where applicable).
+    ///
+    /// Otherwise this is the same as [`from_ext()`](#method.from_ext), which is case-insensitive.
+    pub fn from_ext_case_sensitive(ext: &str) -> MimeGuess {
+        if !is_searchable_ext(ext) {
+            return MimeGuess(&[]);
+        }
+
+        impl_::get_entry(ext)
+            .filter(|&(stored, _)| stored == ext)
+            .map_or(MimeGuess(&[]), |(_, mimes)| MimeGuess(mimes))
+    }
+
+    /// Guess the MIME type of a file with the given extension, after stripping characters which
+    /// commonly contaminate copy-pasted input.
+    ///
+    /// The following are stripped from both ends of `ext` before lookup:
+    ///
+    /// * whitespace, as defined by [`char::is_whitespace()`];
+    /// * the byte order mark / zero width no-break space (`U+FEFF`);
+    /// * zero width space (`U+200B`), zero width non-joiner (`U+200C`), zero width joiner
+    ///   (`U+200D`) and word joiner (`U+2060`).
+    ///
+    /// If any non-ASCII characters remain after stripping, an empty guess is returned.
+    /// Otherwise this is the same as [`from_ext()`](#method.from_ext).
+    ///
+    /// [`char::is_whitespace()`]: https://doc.rust-lang.org/std/primitive.char.html#method.is_whitespace
+    pub fn from_ext_sanitized(ext: &str) -> MimeGuess {
+        let ext = ext.trim_matches(|c: char| {
+            c.is_whitespace()
+                || ['\u{FEFF}', '\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}'].contains(&c)
+        });
+
+        if !ext.is_ascii() {
             return MimeGuess(&[]);
         }
 
-        impl_::get_mime_types(ext).map_or(MimeGuess(&[]), |v| MimeGuess(v))
+        Self::from_ext(ext)
+    }
+
+    /// Guess the MIME type of a file with the given extension, also returning the extension as
+    /// stored in the crate's table (see [`canonical_extension()`](fn.canonical_extension.html)).
+    ///
+    /// The returned extension is `None` exactly when the guess is empty; otherwise it points
+    /// into static data, so inputs differing only in case yield the same pointer and it can be
+    /// used as a cheap cache key for the guess.
+    ///
+    /// As with [`from_ext()`](#method.from_ext), no lookup is attempted for an `ext` longer
+    /// than [`MAX_EXTENSION_LEN`](constant.MAX_EXTENSION_LEN.html) bytes.
+    pub fn from_ext_interned(ext: &str) -> (Option<&'static str>, MimeGuess) {
+        if !is_searchable_ext(ext) {
+            return (None, MimeGuess(&[]));
+        }
+
+        impl_::get_entry(ext).map_or((None, MimeGuess(&[])), |(ext, types)| {
+            (Some(ext), MimeGuess(types))
+        })
+    }
+
+    /// Guess the MIME type of a file with the given extension, keeping track of the extension
+    /// the guess was derived from.
+    ///
+    /// This is the same as [`from_ext()`](#method.from_ext), with the matched extension being
+    /// as stored in the crate's table; see [`TrackedGuess`](struct.TrackedGuess.html).
+    pub fn from_ext_tracked(ext: &str) -> TrackedGuess {
+        let (ext, guess) = Self::from_ext_interned(ext);
+        TrackedGuess { ext, guess }
+    }
+
+    /// Guess the MIME type of a file from a list of alternative extensions in order of
+    /// preference, e.g. `["mjs", "js"]`, keeping track of the extension which was used.
+    ///
+    /// The guess for the first extension with a known MIME type mapping is returned, as by
+    /// [`from_ext_tracked()`](#method.from_ext_tracked); if none has one, the guess is empty.
+    pub fn from_exts<'a, I: IntoIterator<Item = &'a str>>(exts: I) -> TrackedGuess {
+        exts.into_iter()
+            .map(Self::from_ext_tracked)
+            .find(|tracked| !tracked.guess.is_empty())
+            .unwrap_or(TrackedGuess {
+                ext: None,
+                guess: MimeGuess(&[]),
+            })
+    }
+
+    /// Guess the MIME type of `path` by its extension, keeping track of the extension the guess
+    /// was derived from. **No disk access is performed.**
+    ///
+    /// This is the same as [`from_path()`](#method.from_path), with the matched extension being
+    /// as stored in the crate's table; see [`TrackedGuess`](struct.TrackedGuess.html).
+    #[cfg(feature = "path")]
+    pub fn from_path_tracked<P: AsRef<Path>>(path: P) -> TrackedGuess {
+        path_extension(path.as_ref())
+            .and_then(OsStr::to_str)
+            .map_or(
+                TrackedGuess {
+                    ext: None,
+                    guess: MimeGuess(&[]),
+                },
+                Self::from_ext_tracked,
+            )
     }
 
     /// Guess the MIME type of `path` by its extension (as defined by
@@ -76,6 +292,17 @@ impl MimeGuess {
     ///
     /// The search is case-insensitive.
     ///
+    /// ### `.` and `..` Components
+    /// These are handled as by `Path::extension()`, so `./dir/../file.png` resolves `png`.
+    /// A path which *ends* in one of these, e.g. `foo/..` or `foo.png/.`, names a directory
+    /// and so always returns an empty guess.
+    ///
+    /// ### Dotfiles and Stray Dots
+    /// A leading `.` marks a hidden file rather than an extension, so `.gitignore` has no
+    /// extension and returns an empty guess, while `.config.json` resolves `json`. Only the
+    /// text after the *last* `.` is used: a file name ending in `.`, e.g. `archive.`, has an
+    /// empty extension and returns an empty guess, and `archive..gz` and `..gz` resolve `gz`.
+    ///
     /// ## Note
     /// **Guess** is the operative word here, as there are no guarantees that the contents of the
     /// file that `path` points to match the MIME type associated with the path's extension.
@@ -83,13 +310,223 @@ impl MimeGuess {
     /// Take care when processing files with assumptions based on the return value of this function.
     ///
     /// [`Path::extension()`]: https://doc.rust-lang.org/std/path/struct.Path.html#method.extension
+    #[cfg(feature = "path")]
     pub fn from_path<P: AsRef<Path>>(path: P) -> MimeGuess {
-        path.as_ref()
-            .extension()
+        path_extension(path.as_ref())
             .and_then(OsStr::to_str)
             .map_or(MimeGuess(&[]), Self::from_ext)
     }
 
+    /// Guess the MIME types of many paths, e.g. a directory listing, by their extensions.
+    /// **No disk access is performed.**
+    ///
+    /// Each path is yielded back along with its guess, as by [`from_path()`](#method.from_path).
+    /// Lookups compare extensions case-insensitively in place and, on Unix and Windows, never
+    /// allocate (even for paths which are not valid UTF-8), so no scratch buffer is needed and
+    /// this is purely a convenience for pairing paths with guesses.
+    #[cfg(feature = "path")]
+    pub fn from_paths<I, P>(paths: I) -> impl Iterator<Item = (P, MimeGuess)>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        paths.into_iter().map(|path| {
+            let guess = Self::from_path(&path);
+            (path, guess)
+        })
+    }
+
+    /// Guess the MIME type of a path given as raw bytes, e.g. a Unix file name, by its
+    /// extension. **No disk access is performed.**
+    ///
+    /// The file name is everything after the last `/` byte, and the extension everything after
+    /// the last `.` byte of the file name, so only the extension itself has to be valid UTF-8.
+    /// As with [`from_path()`](#method.from_path), a leading `.` does not start an extension
+    /// (`.png` has none) and the search is case-insensitive. Unlike `Path`, trailing slashes
+    /// are not ignored: a path ending in `/` names a directory and yields an empty guess.
+    pub fn from_path_bytes(bytes: &[u8]) -> MimeGuess {
+        let name_start = bytes.iter().rposition(|&b| b == b'/').map_or(0, |i| i + 1);
+        let name = &bytes[name_start..];
+
+        match name.iter().rposition(|&b| b == b'.') {
+            Some(dot) if dot > 0 => str::from_utf8(&name[dot + 1..])
+                .ok()
+                .map_or(MimeGuess(&[]), Self::from_ext),
+            _ => MimeGuess(&[]),
+        }
+    }
+
+    /// Guess the MIME type of `path` by its extension, decoding the extension lossily if it is
+    /// not valid UTF-8. **No disk access is performed.**
+    ///
+    /// Only the extension is ever decoded, so a file name such as `caf\xE9.pdf` (Latin-1) is
+    /// matched as `pdf` here and by [`from_path()`](#method.from_path) alike. For an extension
+    /// which is itself not valid UTF-8, invalid sequences are replaced with `U+FFFD` before
+    /// matching; as no known extension contains that character, such an extension still
+    /// yields an empty guess rather than a different type. The replacement only affects
+    /// matching: the returned guess always consists of media types from the crate's table.
+    #[cfg(feature = "path")]
+    pub fn from_path_lossy<P: AsRef<Path>>(path: P) -> MimeGuess {
+        path_extension(path.as_ref())
+            .map_or(MimeGuess(&[]), |ext| Self::from_ext(&ext.to_string_lossy()))
+    }
+
+    /// Guess the MIME type of the resource at `url` by the extension of the last segment of its
+    /// path. **No network access is performed.**
+    ///
+    /// The query (`?...`) and fragment (`#...`) are ignored, and the last path segment is
+    /// percent-decoded before its extension is taken, so `%2Ehtml` is the same as `.html`.
+    /// `url` may be absolute (`https://host/pic.png`), scheme-relative (`//host/pic.png`) or
+    /// just a path (`/img/pic.png?v=3` or `img//pic.png`). A `//` only starts an authority at
+    /// the very beginning of `url` or directly after its scheme. An empty guess is returned if the URL has no path,
+    /// if the path ends in `/` (i.e. names a directory), or if the decoded segment is not valid
+    /// UTF-8.
+    ///
+    /// As with [`from_path()`](#method.from_path), leading dots are not an extension
+    /// (`/.htaccess` yields an empty guess) and the search is case-insensitive.
+    #[cfg(feature = "path")]
+    pub fn from_url(url: &str) -> MimeGuess {
+        let url = url.split('#').next().unwrap_or("");
+        let url = url.split('?').next().unwrap_or("");
+
+        // a scheme is a letter followed by letters, digits, `+`, `-` or `.`, then `:`
+        // (RFC 3986, Section 3.1); anything else before a `:` is part of a relative path
+        let is_scheme = |scheme: &str| {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        };
+
+        let url = match url.find(':') {
+            Some(colon) if is_scheme(&url[..colon]) => &url[colon + 1..],
+            _ => url,
+        };
+
+        // an authority only follows the scheme directly, or starts a scheme-relative URL
+        let path = if url.starts_with("//") {
+            match url[2..].find('/') {
+                Some(path_start) => &url[2 + path_start..],
+                None => return MimeGuess(&[]),
+            }
+        } else {
+            url
+        };
+
+        let segment = path.rsplit('/').next().unwrap_or("");
+
+        let segment = match String::from_utf8(percent_decode(segment)) {
+            Ok(segment) => segment,
+            Err(_) => return MimeGuess(&[]),
+        };
+
+        match segment.rfind('.') {
+            Some(dot) if dot > 0 => Self::from_ext(&segment[dot + 1..]),
+            _ => MimeGuess(&[]),
+        }
+    }
+
+    /// Guess the MIME type of a file with the given extension, returning an error instead of an
+    /// empty guess.
+    ///
+    /// Returns `GuessError::NoExtension` if `ext` is empty, or `GuessError::UnknownExtension`
+    /// if it has no known MIME type mapping. Otherwise this is the same as
+    /// [`from_ext()`](#method.from_ext).
+    pub fn try_from_ext(ext: &str) -> Result<MimeGuess, GuessError> {
+        if ext.is_empty() {
+            return Err(GuessError::NoExtension);
+        }
+
+        let guess = Self::from_ext(ext);
+
+        if guess.is_empty() {
+            Err(GuessError::UnknownExtension)
+        } else {
+            Ok(guess)
+        }
+    }
+
+    /// Guess the MIME type of `path` by its extension, returning an error instead of an empty
+    /// guess. **No disk access is performed.**
+    ///
+    /// Returns `GuessError::NoExtension` if `path` has no extension (as for
+    /// [`from_path()`](#method.from_path), this includes paths ending in `.` or `..`),
+    /// `GuessError::NonUtf8Extension` if its extension is not valid UTF-8, or
+    /// `GuessError::UnknownExtension` if its extension has no known MIME type mapping.
+    #[cfg(feature = "path")]
+    pub fn try_from_path<P: AsRef<Path>>(path: P) -> Result<MimeGuess, GuessError> {
+        let ext = path_extension(path.as_ref()).ok_or(GuessError::NoExtension)?;
+        let ext = ext.to_str().ok_or(GuessError::NonUtf8Extension)?;
+        Self::try_from_ext(ext)
+    }
+
+    /// Guess the MIME type of `path` by its extension, recognizing compound extensions which
+    /// span several dot-separated segments such as `tar.gz` or `tar.bz2`.
+    /// **No disk access is performed.**
+    ///
+    /// The trailing segments of the file name are tried longest first, so `backup.tar.gz`
+    /// gives the type for a gzipped tarball rather than just gzip. If no known compound
+    /// extension matches, e.g. for `foo.bar.gz`, this is the same as
+    /// [`from_path()`](#method.from_path).
+    ///
+    /// The search is case-insensitive.
+    #[cfg(feature = "path")]
+    pub fn from_path_compound<P: AsRef<Path>>(path: P) -> MimeGuess {
+        Self::from_path_all_extensions(path.as_ref()).map_or(MimeGuess(&[]), |(_, guess)| guess)
+    }
+
+    /// Guess the MIME type of `path` by trying each run of trailing dot-separated segments of
+    /// its file name as an extension, longest first, and returning the first which gives a
+    /// non-empty guess along with that extension. **No disk access is performed.**
+    ///
+    /// For `backup.2024.sql.gz` this tries `2024.sql.gz`, `sql.gz` and then `gz`. The file stem
+    /// (including any leading dots) is never tried, so `.bashrc` and `file` return `None`, as
+    /// does a path with no extension having a known mapping.
+    ///
+    /// The search is case-insensitive.
+    #[cfg(feature = "path")]
+    pub fn from_path_all_extensions<P: AsRef<Path> + ?Sized>(
+        path: &P,
+    ) -> Option<(&str, MimeGuess)> {
+        trailing_extensions(path.as_ref())
+            .map(|ext| (ext, Self::from_ext(ext)))
+            .find(|&(_, guess)| !guess.is_empty())
+    }
+
+    /// Get the MIME type of the file at `path`, including the `inode/*` pseudo-types used by
+    /// desktop environments (see [shared-mime-info]) for things which are not regular files.
+    ///
+    /// **Unlike every other method in this crate, this performs disk access** to read the
+    /// metadata of `path` (without following symlinks). Returns:
+    ///
+    /// * `inode/directory` for directories;
+    /// * `inode/symlink` for symbolic links;
+    /// * on Unix, `inode/fifo`, `inode/socket`, `inode/chardevice` and `inode/blockdevice` for
+    ///   the respective special files;
+    /// * otherwise (including if the metadata cannot be read), the first guess by the extension
+    ///   of `path` as with [`from_path()`](#method.from_path).
+    ///
+    /// [shared-mime-info]: https://specifications.freedesktop.org/shared-mime-info-spec/latest/
+    #[cfg(feature = "path")]
+    pub fn from_path_special<P: AsRef<Path>>(path: P) -> Option<Mime> {
+        let path = path.as_ref();
+
+        let file_type = match fs::symlink_metadata(path) {
+            Ok(metadata) => metadata.file_type(),
+            Err(_) => return Self::from_path(path).first(),
+        };
+
+        let special = if file_type.is_dir() {
+            Some("inode/directory")
+        } else if file_type.is_symlink() {
+            Some("inode/symlink")
+        } else {
+            special_file_type(&file_type)
+        };
+
+        special.map_or_else(|| Self::from_path(path).first(), |s| Some(expect_mime(s)))
+    }
+
     /// `true` if the guess did not return any known mappings for the given path or extension.
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
@@ -100,6 +537,102 @@ impl MimeGuess {
         self.0.len()
     }
 
+    /// `true` if any type in this guess is a font format.
+    ///
+    /// This includes the `font/*` top-level ([RFC 8081]) as well as the legacy font types
+    /// it supersedes: `application/font-*`, `application/x-font-*` and
+    /// `application/vnd.ms-fontobject` (Embedded OpenType).
+    ///
+    /// [RFC 8081]: https://tools.ietf.org/html/rfc8081
+    pub fn is_font(&self) -> bool {
+        self.iter_raw().any(|mime_str| {
+            let essence = essence_str(mime_str);
+            let starts_with = |prefix: &str| {
+                essence
+                    .get(..prefix.len())
+                    .map_or(false, |p| p.eq_ignore_ascii_case(prefix))
+            };
+
+            starts_with("font/")
+                || starts_with("application/font-")
+                || starts_with("application/x-font-")
+                || essence.eq_ignore_ascii_case("application/vnd.ms-fontobject")
+        })
+    }
+
+    /// `true` if the first type in this guess is a textual format, e.g. to decide whether to
+    /// treat content of this type as text.
+    ///
+    /// A type is textual if:
+    ///
+    /// * its top-level is `text`;
+    /// * it is one of `application/json`, `application/xml`, `application/javascript`,
+    ///   `application/ecmascript` or `application/x-sh`;
+    /// * or it has the structured syntax suffix `+json` or `+xml`, as `image/svg+xml` does.
+    ///
+    /// Returns `false` for an empty guess. See [Note: Ordering](#note-ordering) above.
+    pub fn is_text(&self) -> bool {
+        self.first_raw().map_or(false, is_textual)
+    }
+
+    /// `true` if content of the first type in this guess benefits from general-purpose
+    /// compression, e.g. to decide whether to apply `Content-Encoding: gzip`.
+    ///
+    /// Textual types are compressible, as are a few uncompressed binary formats such as
+    /// `image/bmp`, `application/wasm` and `font/ttf`, following the `compressible` field of
+    /// [mime-db](https://github.com/jshttp/mime-db) where it has one. Archives (`application/zip`,
+    /// `application/gzip`, `+zip` types), `font/woff2` and other `image/*`, `audio/*` and
+    /// `video/*` types are assumed to be compressed already, as is any type this crate knows
+    /// nothing more about.
+    ///
+    /// Returns `false` for an empty guess. See [Note: Ordering](#note-ordering) above.
+    pub fn is_compressible(&self) -> bool {
+        self.first_raw().map_or(false, |mime| {
+            compressible::is_compressible(essence_str(mime))
+        })
+    }
+
+    /// `true` if `mime` is one of the types in this guess.
+    ///
+    /// Only the `<type>/<subtype>` essence is compared (case-insensitively); parameters are
+    /// ignored. This does not parse the types in the guess.
+    pub fn contains(&self, mime: &Mime) -> bool {
+        self.contains_str(mime.essence_str())
+    }
+
+    /// `true` if the media type `mime_str` is one of the types in this guess.
+    ///
+    /// Only the `<type>/<subtype>` essence is compared (case-insensitively); parameters are
+    /// ignored. This does not parse `mime_str` or the types in the guess.
+    pub fn contains_str(&self, mime_str: &str) -> bool {
+        let essence = essence_str(mime_str);
+        self.iter_raw()
+            .any(|m| essence_str(m).eq_ignore_ascii_case(essence))
+    }
+
+    /// `true` if it is recommended to send `X-Content-Type-Options: nosniff` alongside a
+    /// `Content-Type` derived from this guess.
+    ///
+    /// This is the case when the guess is empty, or when it contains more than one distinct
+    /// media type (ignoring parameters) so the `Content-Type` sent may not be the one the
+    /// recipient would otherwise infer. A guess of a single type returns `false`.
+    pub fn recommend_nosniff(&self) -> bool {
+        self.is_empty() || self.has_multiple_essences()
+    }
+
+    /// `true` if this guess has more than one distinct `<type>/<subtype>` essence,
+    /// short-circuiting after the second one is found.
+    fn has_multiple_essences(&self) -> bool {
+        let mut essences = self.iter_raw().map(essence_str);
+
+        let first = match essences.next() {
+            Some(first) => first,
+            None => return false,
+        };
+
+        essences.any(|essence| !essence.eq_ignore_ascii_case(first))
+    }
+
     /// Get the first guessed `Mime`, if applicable.
     ///
     /// See [Note: Ordering](#note-ordering) above.
@@ -111,7 +644,150 @@ impl MimeGuess {
     ///
     /// See [Note: Ordering](#note-ordering) above.
     pub fn first_raw(&self) -> Option<&'static str> {
-        self.0.get(0).cloned()
+        self.0.first().cloned()
+    }
+
+    /// Get the `i`th guessed `Mime`, or `None` if `i` is out of bounds.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn get(&self, i: usize) -> Option<Mime> {
+        self.get_raw(i).map(expect_mime)
+    }
+
+    /// Get the `i`th guessed Media Type as a string, or `None` if `i` is out of bounds.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn get_raw(&self, i: usize) -> Option<&'static str> {
+        self.0.get(i).cloned()
+    }
+
+    /// Get all guessed Media Types as a slice of strings, borrowed from the crate's table.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn as_raw_slice(&self) -> &'static [&'static str] {
+        self.0
+    }
+
+    /// Collect all guessed `Mime` values into a `Vec`.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn to_vec(&self) -> Vec<Mime> {
+        self.iter().collect()
+    }
+
+    /// Collect all guessed Media Types as strings into a `Vec`.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn to_raw_vec(&self) -> Vec<&'static str> {
+        self.0.to_vec()
+    }
+
+    /// Get the most trustworthy `Mime` in this guess, if applicable.
+    ///
+    /// This is usually the same as [`first()`](#method.first), but the table does not always
+    /// list the current type first for every extension. Candidates are ranked by, in order of
+    /// precedence:
+    ///
+    /// 1. not being obsolete, i.e. not superseded by another type according to the alias table
+    ///    used by [`canonicalize()`](#method.canonicalize);
+    /// 2. being registered, i.e. neither the top-level nor subtype has the `x-` prefix reserved
+    ///    for unregistered types ([RFC 6838, Section 3.4][rfc6838]);
+    /// 3. position in the guess.
+    ///
+    /// This is a heuristic: the table does not record which types are actually registered with
+    /// IANA (see [Note: No IANA Registration Data](index.html#note-no-iana-registration-data)),
+    /// so the lack of an `x-` prefix stands in for registration. A few unregistered types have
+    /// no such prefix, and a few registered ones still have it.
+    ///
+    /// [rfc6838]: https://tools.ietf.org/html/rfc6838#section-3.4
+    pub fn best(&self) -> Option<Mime> {
+        self.iter_raw()
+            .min_by_key(|mime_str| {
+                let essence = essence_str(mime_str);
+                (
+                    mime_aliases::get_current(essence).is_some(),
+                    is_unregistered(essence),
+                )
+            })
+            .map(expect_mime)
+    }
+
+    /// Get the first guessed `Mime` with the default `charset` parameter for its type appended,
+    /// if applicable.
+    ///
+    /// The default charsets are taken from a curated table, e.g. `utf-8` for `text/plain` or
+    /// `us-ascii` for `application/pgp-signature`; if the first type has no default charset it
+    /// is returned as-is.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn first_with_default_charset(&self) -> Option<Mime> {
+        let first = self.first_raw()?;
+
+        Some(match charsets::get_default_charset(essence_str(first)) {
+            Some(charset) => expect_mime(&format!("{}; charset={}", first, charset)),
+            None => expect_mime(first),
+        })
+    }
+
+    /// Get the charset implied for content of the first guessed type when no charset is
+    /// declared, if applicable.
+    ///
+    /// This is the type's default charset from a curated table (as used by
+    /// [`first_with_default_charset()`](#method.first_with_default_charset)), e.g. `utf-8` for
+    /// `application/json` and `image/svg+xml` or `us-ascii` for `application/pgp-signature`.
+    /// Any other `text/*` type implies `utf-8`. Binary types, and an empty guess, give `None`.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn implied_charset(&self) -> Option<&'static str> {
+        self.first_raw().and_then(implied_charset)
+    }
+
+    /// Get an iterator over the `Mime` values contained in this guess, each paired with its
+    /// implied charset as given by [`implied_charset()`](#method.implied_charset).
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn iter_with_charsets(&self) -> impl Iterator<Item = (Mime, Option<&'static str>)> {
+        self.iter_raw()
+            .map(|mime_str| (expect_mime(mime_str), implied_charset(mime_str)))
+    }
+
+    /// Get the first guessed `Mime` for use in a `Content-Type` header, with `charset=utf-8`
+    /// appended if it is a textual type, if applicable.
+    ///
+    /// This is shorthand for [`preferred_with_charset("utf-8")`](#method.preferred_with_charset).
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn preferred(&self) -> Option<Mime> {
+        self.preferred_with_charset("utf-8")
+    }
+
+    /// Get the first guessed `Mime` with the given `charset` parameter appended if it is a
+    /// textual type, if applicable.
+    ///
+    /// Textual types are those for which [`implied_charset()`](#method.implied_charset) returns
+    /// a charset, e.g. `text/*`, `application/json` or `image/svg+xml`. Other types are
+    /// returned as-is.
+    ///
+    /// Returns `None` if the guess is empty, or if `charset` is not a valid token
+    /// ([RFC 7230, Section 3.2.6][rfc7230]), e.g. if it is empty or contains whitespace or `;`.
+    /// This is checked for every type, so an invalid charset is caught even when it would not
+    /// be used.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    ///
+    /// [rfc7230]: https://tools.ietf.org/html/rfc7230#section-3.2.6
+    pub fn preferred_with_charset(&self, charset: &str) -> Option<Mime> {
+        if !is_token(charset) {
+            return None;
+        }
+
+        let first = self.first_raw()?;
+
+        if self.implied_charset().is_none() {
+            return Some(expect_mime(first));
+        }
+
+        format!("{}; charset={}", first, charset).parse().ok()
     }
 
     /// Get the first guessed `Mime`, or if the guess is empty, return
@@ -136,6 +812,25 @@ impl MimeGuess {
         self.first_or(mime::APPLICATION_OCTET_STREAM)
     }
 
+    /// Get the first guessed `Mime`, or if the guess is empty, return
+    /// [`application/octet-stream`] instead, along with whether that fallback was used.
+    ///
+    /// This distinguishes an unknown extension, for which an HTTP application may
+    /// [prefer][rfc7231] to omit the `Content-Type` header, from an extension which really
+    /// maps to `application/octet-stream`, such as `bin`: the flag is `true` only in the
+    /// former case.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    ///
+    /// [`application/octet-stream`]: https://docs.rs/mime/0.3/mime/constant.APPLICATION_OCTET_STREAM.html
+    /// [rfc7231]: https://tools.ietf.org/html/rfc7231#section-3.1.1.5
+    pub fn first_or_octet_stream_marked(&self) -> (Mime, bool) {
+        match self.first() {
+            Some(first) => (first, false),
+            None => (mime::APPLICATION_OCTET_STREAM, true),
+        }
+    }
+
     /// Get the first guessed `Mime`, or if the guess is empty, return
     /// [`text/plain`](::mime::TEXT_PLAIN) instead.
     ///
@@ -175,6 +870,105 @@ impl MimeGuess {
     pub fn iter_raw(&self) -> IterRaw {
         IterRaw(self.0.iter().cloned())
     }
+
+    /// Get the `Mime` values contained in this guess, grouped so that top-level types appear in
+    /// the order given by `order` (compared case-insensitively), e.g. `&["image", "text"]`.
+    ///
+    /// Types with a top-level not listed in `order` come last. Within each group the order
+    /// of the guess is preserved; see [Note: Ordering](#note-ordering) above.
+    pub fn iter_sorted_by_top_level(&self, order: &[&str]) -> vec::IntoIter<Mime> {
+        let mut mimes: Vec<Mime> = self.iter().collect();
+
+        mimes.sort_by_key(|mime| {
+            order
+                .iter()
+                .position(|top| top.eq_ignore_ascii_case(mime.type_().as_str()))
+                .unwrap_or(order.len())
+        });
+
+        mimes.into_iter()
+    }
+
+    /// Get a copy of this guess with obsolete media types replaced by their current
+    /// equivalents, e.g. `application/javascript` with `text/javascript`.
+    ///
+    /// Duplicates resulting from the replacement are removed, keeping the first occurrence, and
+    /// the order is otherwise preserved. Parameters are not retained for replaced types.
+    ///
+    /// ### Aliases
+    /// Replacements are taken from a curated table of obsolete types, currently covering the
+    /// JavaScript/ECMAScript aliases listed in RFC 9239, the legacy `application/font-*` types
+    /// (RFC 8081), `application/x-gzip` (RFC 6713), `text/x-markdown` (RFC 7763), `text/xml`
+    /// (RFC 7303), the unregistered YAML types (RFC 9512) and the common misspelling `image/jpg`.
+    pub fn canonicalize(&self) -> OwnedMimeGuess {
+        let mut canonical: Vec<String> = Vec::with_capacity(self.count());
+
+        for mime_str in self.iter_raw() {
+            let mime_str = mime_aliases::get_current(essence_str(mime_str)).unwrap_or(mime_str);
+
+            if !canonical.iter().any(|m| m.eq_ignore_ascii_case(mime_str)) {
+                canonical.push(mime_str.to_owned());
+            }
+        }
+
+        OwnedMimeGuess(canonical)
+    }
+
+    /// Suggest a file name for content of the first guessed type, by appending its
+    /// [preferred extension](fn.preferred_extension.html) to `stem`, e.g. `report.pdf` for
+    /// `report` and `application/pdf`.
+    ///
+    /// If `stem` already has an extension which maps to that type, such as `photo.JPEG` for
+    /// `image/jpeg`, it is returned unchanged rather than doubling the extension.
+    ///
+    /// Returns `None` if the guess is empty or the first type has no known extension.
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    #[cfg(feature = "rev-mappings")]
+    pub fn suggested_filename(&self, stem: &str) -> Option<String> {
+        let first = self.first()?;
+        let ext = preferred_extension(&first)?;
+
+        if MimeGuess::from_path_bytes(stem.as_bytes()).contains(&first) {
+            return Some(stem.to_owned());
+        }
+
+        Some(format!("{}.{}", stem, ext))
+    }
+
+    /// Combine this guess with `other`, e.g. a guess from the content of a file, into one guess.
+    ///
+    /// The types of this guess come first, followed by those of `other` which are not
+    /// already present (compared case-insensitively), both in their original order.
+    pub fn merge(self, other: MimeGuess) -> OwnedMimeGuess {
+        let mut merged: Vec<String> = Vec::with_capacity(self.count() + other.count());
+
+        for mime_str in self.iter_raw().chain(other.iter_raw()) {
+            if !merged.iter().any(|m| m.eq_ignore_ascii_case(mime_str)) {
+                merged.push(mime_str.to_owned());
+            }
+        }
+
+        OwnedMimeGuess(merged)
+    }
+
+    /// Get an iterator over the `Mime` values contained in this guess, paired with their index.
+    ///
+    /// The candidate at index `0` is the same one returned by [`first()`](#method.first).
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn enumerate(&self) -> iter::Enumerate<Iter> {
+        self.iter().enumerate()
+    }
+
+    /// Get an iterator over the raw media-type strings in this guess, paired with their index.
+    ///
+    /// The candidate at index `0` is the same one returned by [`first_raw()`](#method.first_raw).
+    ///
+    /// See [Note: Ordering](#note-ordering) above.
+    pub fn enumerate_raw(&self) -> iter::Enumerate<IterRaw> {
+        self.iter_raw().enumerate()
+    }
 }
 
 impl IntoIterator for MimeGuess {
@@ -186,7 +980,7 @@ impl IntoIterator for MimeGuess {
     }
 }
 
-impl<'a> IntoIterator for &'a MimeGuess {
+impl IntoIterator for &MimeGuess {
     type Item = Mime;
     type IntoIter = Iter;
 
@@ -195,14 +989,174 @@ impl<'a> IntoIterator for &'a MimeGuess {
     }
 }
 
-/// An iterator over the `Mime` types of a `MimeGuess`.
+/// Get the `i`th guessed Media Type as a string.
 ///
-/// See [Note: Ordering on `MimeGuess`](struct.MimeGuess.html#note-ordering).
-#[derive(Clone, Debug)]
-pub struct Iter(iter::Map<IterRaw, fn(&'static str) -> Mime>);
+/// `Index` has to return a reference, so this cannot produce a `Mime`; use
+/// [`MimeGuess::get()`](struct.MimeGuess.html#method.get) for that.
+///
+/// # Panics
+/// If `i` is out of bounds, like indexing a slice.
+impl ops::Index<usize> for MimeGuess {
+    type Output = str;
 
-impl Iterator for Iter {
-    type Item = Mime;
+    fn index(&self, i: usize) -> &str {
+        self.0[i]
+    }
+}
+
+impl fmt::Display for MimeGuess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_media_types(self.iter_raw(), f)
+    }
+}
+
+/// A [`MimeGuess`](struct.MimeGuess.html) along with the known extension it was derived from.
+///
+/// Returned by [`MimeGuess::from_ext_tracked()`](struct.MimeGuess.html#method.from_ext_tracked),
+/// [`MimeGuess::from_exts()`](struct.MimeGuess.html#method.from_exts)
+/// and [`MimeGuess::from_path_tracked()`](struct.MimeGuess.html#method.from_path_tracked).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TrackedGuess {
+    ext: Option<&'static str>,
+    guess: MimeGuess,
+}
+
+impl TrackedGuess {
+    /// Get the extension the guess was derived from, as stored in the crate's table
+    /// (lowercase where applicable), e.g. `jpg` for `a.JPG`.
+    ///
+    /// `None` if and only if the guess is empty.
+    pub fn matched_extension(&self) -> Option<&'static str> {
+        self.ext
+    }
+
+    /// Get the guess itself.
+    pub fn guess(&self) -> MimeGuess {
+        self.guess
+    }
+}
+
+impl From<TrackedGuess> for MimeGuess {
+    fn from(tracked: TrackedGuess) -> MimeGuess {
+        tracked.guess
+    }
+}
+
+/// The reason no MIME type could be guessed, returned by
+/// [`MimeGuess::try_from_ext()`](struct.MimeGuess.html#method.try_from_ext) and
+/// [`MimeGuess::try_from_path()`](struct.MimeGuess.html#method.try_from_path).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GuessError {
+    /// The extension was empty, or the path had no extension.
+    NoExtension,
+    /// The extension of the path was not valid UTF-8.
+    NonUtf8Extension,
+    /// The extension has no known MIME type mapping.
+    UnknownExtension,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            GuessError::NoExtension => "no file extension",
+            GuessError::NonUtf8Extension => "file extension is not valid UTF-8",
+            GuessError::UnknownExtension => "unknown file extension",
+        })
+    }
+}
+
+impl error::Error for GuessError {}
+
+/// Types which a [`MimeGuess`](struct.MimeGuess.html) can be produced from.
+///
+/// This allows accepting any input which can be guessed from generically:
+///
+/// ```
+/// # extern crate mime_guess;
+/// use mime_guess::Guessable;
+///
+/// fn content_type<G: Guessable>(input: G) -> String {
+///     input.guess().first_or_octet_stream().to_string()
+/// }
+///
+/// # #[cfg(feature = "path")]
+/// assert_eq!(content_type(std::path::Path::new("foo.gif")), "image/gif");
+/// assert_eq!(content_type("gif"), "image/gif");
+/// ```
+///
+/// ### Implementations
+/// * With the `path` feature, `Path` is treated as a path, as with
+///   [`MimeGuess::from_path()`](struct.MimeGuess.html#method.from_path).
+/// * `str` and `OsStr` are treated as a bare extension, as with
+///   [`MimeGuess::from_ext()`](struct.MimeGuess.html#method.from_ext);
+///   an `OsStr` which is not valid UTF-8 gives an empty guess.
+/// * `Mime` gives a guess of just that type if it is known to the crate (ignoring parameters),
+///   otherwise an empty guess. This requires a linear search of the table.
+/// * References to any of the above.
+pub trait Guessable {
+    /// Guess the MIME type(s) of `self`.
+    fn guess(&self) -> MimeGuess;
+}
+
+#[cfg(feature = "path")]
+impl Guessable for Path {
+    fn guess(&self) -> MimeGuess {
+        MimeGuess::from_path(self)
+    }
+}
+
+impl Guessable for str {
+    fn guess(&self) -> MimeGuess {
+        MimeGuess::from_ext(self)
+    }
+}
+
+impl Guessable for OsStr {
+    fn guess(&self) -> MimeGuess {
+        MimeGuess::from_os_ext(self)
+    }
+}
+
+impl Guessable for Mime {
+    fn guess(&self) -> MimeGuess {
+        let essence = self.essence_str();
+
+        impl_::entries()
+            .filter_map(|(_, mimes)| {
+                mimes
+                    .iter()
+                    .position(|m| m.eq_ignore_ascii_case(essence))
+                    .map(|i| MimeGuess(&mimes[i..=i]))
+            })
+            .next()
+            .unwrap_or(MimeGuess(&[]))
+    }
+}
+
+impl<G: Guessable + ?Sized> Guessable for &G {
+    fn guess(&self) -> MimeGuess {
+        (**self).guess()
+    }
+}
+
+/// An iterator over the `Mime` types of a `MimeGuess`.
+///
+/// See [Note: Ordering on `MimeGuess`](struct.MimeGuess.html#note-ordering).
+///
+/// This is cheap to clone, so it can be saved and resumed mid-traversal;
+/// [`peek()`](#method.peek) looks at the next item without advancing.
+#[derive(Clone, Debug)]
+pub struct Iter(iter::Map<IterRaw, fn(&'static str) -> Mime>);
+
+impl Iter {
+    /// Get the next `Mime` without advancing the iterator.
+    pub fn peek(&self) -> Option<Mime> {
+        self.0.clone().next()
+    }
+}
+
+impl Iterator for Iter {
+    type Item = Mime;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next()
@@ -230,9 +1184,19 @@ impl ExactSizeIterator for Iter {
 /// An iterator over the raw media type strings of a `MimeGuess`.
 ///
 /// See [Note: Ordering on `MimeGuess`](struct.MimeGuess.html#note-ordering).
+///
+/// This is cheap to clone, so it can be saved and resumed mid-traversal;
+/// [`peek()`](#method.peek) looks at the next item without advancing.
 #[derive(Clone, Debug)]
 pub struct IterRaw(iter::Cloned<slice::Iter<'static, &'static str>>);
 
+impl IterRaw {
+    /// Get the next media type string without advancing the iterator.
+    pub fn peek(&self) -> Option<&'static str> {
+        self.0.clone().next()
+    }
+}
+
 impl Iterator for IterRaw {
     type Item = &'static str;
 
@@ -259,6 +1223,190 @@ impl ExactSizeIterator for IterRaw {
     }
 }
 
+/// `Path::extension()`, except that paths ending in a `.` component have no extension
+/// (`Path` ignores it, so `foo.png/.` would otherwise have the extension `png`).
+#[cfg(feature = "path")]
+fn path_extension(path: &Path) -> Option<&OsStr> {
+    if ends_with_cur_dir(path.as_os_str()) {
+        return None;
+    }
+
+    path.extension()
+}
+
+/// `true` if `path` ends in a `.` component, i.e. its last character is `.` and the one before
+/// it (if any) is a separator.
+///
+/// Only the last two characters are inspected, without allocating even if `path` is not valid
+/// UTF-8 (except on platforms other than Unix and Windows, where it is converted lossily).
+#[cfg(feature = "path")]
+fn ends_with_cur_dir(path: &OsStr) -> bool {
+    fn check<I: Iterator<Item = char>>(mut rev_chars: I) -> bool {
+        rev_chars.next() == Some('.') && rev_chars.next().map_or(true, path::is_separator)
+    }
+
+    if let Some(path_str) = path.to_str() {
+        return check(path_str.chars().rev());
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        // non-ASCII bytes become Latin-1 characters, which are never `.` or a separator
+        check(path.as_bytes().iter().rev().map(|&b| char::from(b)))
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        let (second_last, last) = path
+            .encode_wide()
+            .fold((None, None), |(_, last), unit| (last, Some(unit)));
+        // unpaired surrogates are never `.` or a separator
+        let to_char = |unit: u16| std::char::from_u32(u32::from(unit)).unwrap_or('\u{FFFD}');
+        check(last.into_iter().chain(second_last).map(to_char))
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        check(path.to_string_lossy().chars().rev())
+    }
+}
+
+/// `true` if `ext` could be in the table at all, i.e. it is neither empty nor longer than
+/// `MAX_EXTENSION_LEN`. Every lookup by extension checks this first so that arbitrarily long
+/// input is never searched for.
+fn is_searchable_ext(ext: &str) -> bool {
+    !ext.is_empty() && ext.len() <= MAX_EXTENSION_LEN
+}
+
+/// `true` if `s` is a non-empty `token` as defined by RFC 7230, Section 3.2.6.
+fn is_token(s: &str) -> bool {
+    const DELIMITERS: &[u8] = b"\"(),/:;<=>?@[\\]{}";
+
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_graphic() && !DELIMITERS.contains(&b))
+}
+
+/// `true` if `mime_str` is a textual format; see `MimeGuess::is_text()`.
+fn is_textual(mime_str: &str) -> bool {
+    const TEXTUAL_APPLICATION_SUBTYPES: &[&str] =
+        &["ecmascript", "javascript", "json", "x-sh", "xml"];
+
+    let essence = essence_str(mime_str);
+    let (top, sub) = match essence.find('/') {
+        Some(idx) => (&essence[..idx], &essence[idx + 1..]),
+        None => return false,
+    };
+
+    let suffix = sub.rfind('+').map(|plus| &sub[plus + 1..]);
+
+    top.eq_ignore_ascii_case("text")
+        || (top.eq_ignore_ascii_case("application")
+            && TEXTUAL_APPLICATION_SUBTYPES
+                .iter()
+                .any(|s| s.eq_ignore_ascii_case(sub)))
+        || suffix.map_or(false, |suffix| {
+            suffix.eq_ignore_ascii_case("json") || suffix.eq_ignore_ascii_case("xml")
+        })
+}
+
+/// Get the charset implied for `mime_str`; see `MimeGuess::implied_charset()`.
+fn implied_charset(mime_str: &str) -> Option<&'static str> {
+    let essence = essence_str(mime_str);
+
+    charsets::get_default_charset(essence).or_else(|| {
+        let top = essence.split('/').next().unwrap_or("");
+        if top.eq_ignore_ascii_case("text") {
+            Some("utf-8")
+        } else {
+            None
+        }
+    })
+}
+
+/// Write `mimes` separated by `, `, as in the `Display` form of a guess.
+fn fmt_media_types<'a, I: Iterator<Item = &'a str>>(
+    mimes: I,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    for (i, mime) in mimes.enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+
+        f.write_str(mime)?;
+    }
+
+    Ok(())
+}
+
+/// Decode `%XX` escapes in `s`; malformed escapes are left as-is.
+#[cfg(feature = "path")]
+fn percent_decode(s: &str) -> Vec<u8> {
+    fn hex_val(b: u8) -> Option<u8> {
+        (b as char).to_digit(16).map(|d| d as u8)
+    }
+
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(hi), Some(lo)) = (hex_val(bytes[i + 1]), hex_val(bytes[i + 2])) {
+                decoded.push(hi << 4 | lo);
+                i += 3;
+                continue;
+            }
+        }
+
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    decoded
+}
+
+/// Get the runs of trailing dot-separated segments of the file name of `path`, longest first,
+/// e.g. `tar.gz` then `gz` for `archive.tar.gz`.
+///
+/// Leading dots are part of the file stem and the stem is never included, both as with
+/// `Path::extension()`; this yields nothing if `path_extension()` would return `None`.
+#[cfg(feature = "path")]
+fn trailing_extensions<'a>(path: &'a Path) -> impl Iterator<Item = &'a str> + 'a {
+    let name = path_extension(path)
+        .and(path.file_name())
+        .and_then(OsStr::to_str)
+        .map_or("", |name| name.trim_start_matches('.'));
+
+    name.match_indices('.')
+        .map(move |(idx, _)| &name[idx + 1..])
+}
+
+#[cfg(all(feature = "path", unix))]
+fn special_file_type(file_type: &fs::FileType) -> Option<&'static str> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if file_type.is_fifo() {
+        Some("inode/fifo")
+    } else if file_type.is_socket() {
+        Some("inode/socket")
+    } else if file_type.is_char_device() {
+        Some("inode/chardevice")
+    } else if file_type.is_block_device() {
+        Some("inode/blockdevice")
+    } else {
+        None
+    }
+}
+
+#[cfg(all(feature = "path", not(unix)))]
+fn special_file_type(_file_type: &fs::FileType) -> Option<&'static str> {
+    None
+}
+
 fn expect_mime(s: &str) -> Mime {
     // `.parse()` should be checked at compile time to never fail
     s.parse()
@@ -271,10 +1419,315 @@ pub fn from_ext(ext: &str) -> MimeGuess {
 }
 
 /// Wrapper of [`MimeGuess::from_path()`](struct.MimeGuess.html#method.from_path).
+#[cfg(feature = "path")]
 pub fn from_path<P: AsRef<Path>>(path: P) -> MimeGuess {
     MimeGuess::from_path(path)
 }
 
+/// Get an iterator over every extension with a known MIME type mapping, each exactly once.
+///
+/// Extensions are yielded as stored in the crate's table (lowercase where applicable);
+/// the order is unspecified.
+pub fn all_extensions() -> impl Iterator<Item = &'static str> {
+    impl_::entries().map(|(ext, _)| ext)
+}
+
+/// Get an iterator over every distinct media-type string which a guess may contain, in
+/// lexicographic order.
+///
+/// This has to collect the whole table up-front to deduplicate it.
+pub fn all_mime_types() -> impl Iterator<Item = &'static str> {
+    impl_::entries()
+        .flat_map(|(_, mimes)| mimes.iter().cloned())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+}
+
+/// Statistics about the crate's compiled-in table; see [`table_stats()`](fn.table_stats.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TableStats {
+    /// The number of extensions with a known MIME type mapping, as yielded by
+    /// [`all_extensions()`](fn.all_extensions.html).
+    pub extensions: usize,
+    /// The number of distinct media-type strings, as yielded by
+    /// [`all_mime_types()`](fn.all_mime_types.html).
+    pub mime_types: usize,
+    /// The approximate in-memory size of the forward table in bytes: its entries, the slices of
+    /// media types they point to, and the text of every string, counted once per occurrence.
+    ///
+    /// Reverse mappings are not included.
+    pub bytes: usize,
+}
+
+/// Get statistics about the crate's compiled-in table, e.g. to compare builds with different
+/// features enabled.
+///
+/// This walks the whole table and collects it to count distinct media types, so it is not
+/// intended to be called often.
+pub fn table_stats() -> TableStats {
+    let mut bytes = 0;
+    let mut mime_types = BTreeSet::new();
+
+    let extensions = impl_::entries()
+        .map(|(ext, mimes)| {
+            bytes += mem::size_of::<(&str, &[&str])>() + ext.len();
+            bytes += mem::size_of_val(mimes);
+
+            for &mime in mimes {
+                bytes += mime.len();
+                mime_types.insert(mime);
+            }
+        })
+        .count();
+
+    TableStats {
+        extensions,
+        mime_types: mime_types.len(),
+        bytes,
+    }
+}
+
+/// Write the crate's table in the `mime.types` format used by Apache and others.
+///
+/// Each line names a media type followed by all of its extensions, separated by whitespace.
+/// Lines are sorted by media type, and extensions by the order they are stored in the table.
+/// The output can be read back with
+/// [`MimeRegistry::load_mime_types()`](struct.MimeRegistry.html#method.load_mime_types).
+pub fn write_mime_types<W: Write>(w: &mut W) -> io::Result<()> {
+    let mut mime_exts = BTreeMap::<_, Vec<_>>::new();
+
+    for (ext, mimes) in impl_::entries() {
+        for &mime in mimes {
+            mime_exts.entry(mime).or_default().push(ext);
+        }
+    }
+
+    for (mime, exts) in mime_exts {
+        write!(w, "{}", mime)?;
+
+        for ext in exts {
+            write!(w, " {}", ext)?;
+        }
+
+        writeln!(w)?;
+    }
+
+    Ok(())
+}
+
+/// Get the canonical spelling of `ext` if it is a common variant of another extension, e.g.
+/// `jpg` for `jpeg` or `html` for `htm`, or `ext` itself (unchanged) otherwise.
+///
+/// The search is case-insensitive; canonical extensions are returned in lowercase. Unlike
+/// [`resolve_with_alias()`](fn.resolve_with_alias.html), no MIME lookup is performed, so
+/// unknown extensions simply pass through.
+pub fn normalize_extension(ext: &str) -> &str {
+    ext_aliases::get_canonical(ext).unwrap_or(ext)
+}
+
+/// Get the current equivalent of `mime` if it is an obsolete media type, e.g. `text/javascript`
+/// for `application/x-javascript`, or `mime` itself otherwise.
+///
+/// Parameters are retained, so `image/jpg; q=0.9` gives `image/jpeg; q=0.9`. The aliases
+/// are the same as for [`MimeGuess::canonicalize()`](struct.MimeGuess.html#method.canonicalize);
+/// see there for the list.
+pub fn canonicalize(mime: &Mime) -> Mime {
+    let mime_str = mime.as_ref();
+    let essence = essence_str(mime_str);
+
+    match mime_aliases::get_current(essence) {
+        Some(current) => {
+            let params = mime_str.find(';').map_or("", |i| &mime_str[i..]);
+            expect_mime(&format!("{}{}", current, params))
+        }
+        None => mime.clone(),
+    }
+}
+
+/// `true` if `ext` has a known MIME type mapping. Case-insensitive.
+///
+/// This is the same as `!from_ext(ext).is_empty()` but states the intent; no `Mime` is parsed.
+pub fn is_known_extension(ext: &str) -> bool {
+    !MimeGuess::from_ext(ext).is_empty()
+}
+
+/// Get the known extension matching `ext` (case-insensitively) as it is stored in the crate's
+/// table, e.g. `"png"` for `"PNG"`.
+///
+/// Returns `None` if `ext` is empty or has no known MIME type mapping.
+pub fn canonical_extension(ext: &str) -> Option<&'static str> {
+    MimeGuess::from_ext_interned(ext).0
+}
+
+/// Guess the MIME type of a file with the given extension, also returning the canonical
+/// extension it is equivalent to.
+///
+/// If `ext` is an alternative spelling of another extension, e.g. `jpe` or `jpeg` for `jpg`,
+/// the canonical extension is returned. Otherwise this is the same as
+/// [`MimeGuess::from_ext_interned()`](struct.MimeGuess.html#method.from_ext_interned), returning
+/// `ext` itself as stored in the crate's table, or `None` if the guess is empty.
+///
+/// The search is case-insensitive.
+pub fn resolve_with_alias(ext: &str) -> (Option<&'static str>, MimeGuess) {
+    match ext_aliases::get_canonical(ext) {
+        Some(canonical) => (Some(canonical), MimeGuess::from_ext(canonical)),
+        None => MimeGuess::from_ext_interned(ext),
+    }
+}
+
+/// The letter case to display an extension in; see [`display_extension()`](fn.display_extension.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CaseStyle {
+    /// All ASCII letters uppercased, e.g. `PNG`.
+    Upper,
+    /// All ASCII letters lowercased, e.g. `png`.
+    Lower,
+    /// Exactly as stored in the crate's table; see [`canonical_extension()`](fn.canonical_extension.html).
+    AsStored,
+}
+
+/// Get the known extension matching `ext` (case-insensitively) in the given letter case,
+/// for display purposes.
+///
+/// Returns `None` if `ext` is empty or has no known MIME type mapping.
+pub fn display_extension(ext: &str, style: CaseStyle) -> Option<String> {
+    let ext = canonical_extension(ext)?;
+
+    Some(match style {
+        CaseStyle::Upper => ext.to_ascii_uppercase(),
+        CaseStyle::Lower => ext.to_ascii_lowercase(),
+        CaseStyle::AsStored => ext.to_owned(),
+    })
+}
+
+/// `true` if `ext` maps to more than one distinct media type, ignoring parameters
+/// (i.e. comparing only the `<type>/<subtype>` essence, case-insensitively).
+///
+/// Returns `false` for empty or unknown extensions.
+pub fn is_ambiguous_ext(ext: &str) -> bool {
+    from_ext(ext).has_multiple_essences()
+}
+
+/// Construct a `multipart/<sub>` media type with the given `boundary` parameter,
+/// e.g. `multipart/form-data; boundary=xyz`.
+///
+/// Returns `None` if `sub` is not a valid subtype or `boundary` is invalid.
+///
+/// ### Boundary Validation
+/// Per [RFC 2046, Section 5.1.1][rfc2046], the boundary must be 1 to 70 characters long,
+/// consisting only of ASCII letters, digits, spaces and ``'()+_,-./:=?``, and must not end with
+/// a space. The boundary is quoted in the returned type if it contains any characters which
+/// are not allowed in a bare parameter value.
+///
+/// [rfc2046]: https://tools.ietf.org/html/rfc2046#section-5.1.1
+pub fn multipart_with_boundary(sub: &str, boundary: &str) -> Option<Mime> {
+    fn is_bchar_nospace(c: char) -> bool {
+        c.is_ascii_alphanumeric() || "'()+_,-./:=?".contains(c)
+    }
+
+    fn is_tspecial(c: char) -> bool {
+        "()<>@,;:\\\"/[]?= ".contains(c)
+    }
+
+    if boundary.is_empty()
+        || boundary.len() > 70
+        || boundary.ends_with(' ')
+        || !boundary.chars().all(|c| c == ' ' || is_bchar_nospace(c))
+    {
+        return None;
+    }
+
+    if sub.is_empty() || sub.contains(|c: char| c == ';' || c == '/') {
+        return None;
+    }
+
+    let mime_str = if boundary.contains(is_tspecial) {
+        format!("multipart/{}; boundary=\"{}\"", sub, boundary)
+    } else {
+        format!("multipart/{}; boundary={}", sub, boundary)
+    };
+
+    mime_str.parse().ok()
+}
+
+/// `true` if the extensions `a` and `b` map to the same set of media types, ignoring order,
+/// parameters and case, e.g. `jpg` and `jpeg`.
+///
+/// Returns `false` if either extension is empty or unknown.
+pub fn ext_equivalent(a: &str, b: &str) -> bool {
+    let (a, b) = (from_ext(a), from_ext(b));
+
+    fn is_subset(a: MimeGuess, b: MimeGuess) -> bool {
+        a.iter_raw().all(|a_mime| {
+            b.iter_raw()
+                .any(|b_mime| essence_str(a_mime).eq_ignore_ascii_case(essence_str(b_mime)))
+        })
+    }
+
+    !a.is_empty() && !b.is_empty() && is_subset(a, b) && is_subset(b, a)
+}
+
+/// Get every plausible extension interpretation of the filename `name`, with its guess.
+///
+/// Intended for analysis tools and human review; most applications should use
+/// [`from_path()`](fn.from_path.html) instead.
+///
+/// ### Segmentation
+/// Leading dots are considered part of the name (as in `.bashrc`), then the rest of `name` is
+/// split on `.`; the first segment is the file stem and is never treated as an extension.
+/// The candidates are then, in order:
+///
+/// 1. the last segment, which is what [`Path::extension()`] would return;
+/// 2. each longer trailing run of segments, shortest to longest (e.g. `tar.gz`);
+/// 3. each remaining individual segment, right to left.
+///
+/// Only candidates with a non-empty guess are returned. For `report.2024.final.docx` this
+/// checks `docx`, `final.docx`, `2024.final.docx`, `final` and `2024`, in that order.
+///
+/// [`Path::extension()`]: https://doc.rust-lang.org/std/path/struct.Path.html#method.extension
+pub fn all_interpretations(name: &str) -> Vec<(&str, MimeGuess)> {
+    let name = name.trim_start_matches('.');
+    let dots: Vec<usize> = name.match_indices('.').map(|(idx, _)| idx).collect();
+
+    let tails = dots.iter().rev().map(|&dot| &name[dot + 1..]);
+
+    let inner = dots
+        .iter()
+        .zip(dots.iter().skip(1))
+        .rev()
+        .map(|(&start, &end)| &name[start + 1..end]);
+
+    tails
+        .chain(inner)
+        .map(|ext| (ext, from_ext(ext)))
+        .filter(|&(_, guess)| !guess.is_empty())
+        .collect()
+}
+
+/// Strip a single leading `*.` or `.` from `ext`.
+fn strip_leading_dot(ext: &str) -> &str {
+    if ext.starts_with("*.") {
+        &ext[2..]
+    } else if ext.starts_with('.') {
+        &ext[1..]
+    } else {
+        ext
+    }
+}
+
+/// `true` if either the top-level or subtype of `essence` has the `x-` prefix.
+fn is_unregistered(essence: &str) -> bool {
+    essence.split('/').any(|part| {
+        part.get(..2)
+            .map_or(false, |prefix| prefix.eq_ignore_ascii_case("x-"))
+    })
+}
+
+/// Strip any parameters from a media-type string, leaving just `<type>/<subtype>`.
+fn essence_str(mime_str: &str) -> &str {
+    mime_str.split(';').next().unwrap_or(mime_str).trim()
+}
+
 /// Guess the MIME type of `path` by its extension (as defined by `Path::extension()`).
 ///
 /// If `path` has no extension, or its extension has no known MIME type mapping,
@@ -290,6 +1743,7 @@ pub fn from_path<P: AsRef<Path>>(path: P) -> MimeGuess {
 /// header at all instead of defaulting to `application/octet-stream`.
 ///
 /// [rfc7231]: https://tools.ietf.org/html/rfc7231#section-3.1.1.5
+#[cfg(all(feature = "path", not(feature = "no-deprecated")))]
 #[deprecated(
     since = "2.0.0",
     note = "Use `from_path(path).first_or_octet_stream()` instead"
@@ -303,6 +1757,7 @@ pub fn guess_mime_type<P: AsRef<Path>>(path: P) -> Mime {
 /// If `path` has no extension, or its extension has no known MIME type mapping,
 /// then `None` is returned.
 ///
+#[cfg(all(feature = "path", not(feature = "no-deprecated")))]
 #[deprecated(since = "2.0.0", note = "Use `from_path(path).first()` instead")]
 pub fn guess_mime_type_opt<P: AsRef<Path>>(path: P) -> Option<Mime> {
     from_path(path).first()
@@ -318,6 +1773,7 @@ pub fn guess_mime_type_opt<P: AsRef<Path>>(path: P) -> Option<Mime> {
 /// that `path` points to match the MIME type associated with the path's extension.
 ///
 /// Take care when processing files with assumptions based on the return value of this function.
+#[cfg(all(feature = "path", not(feature = "no-deprecated")))]
 #[deprecated(since = "2.0.0", note = "Use `from_path(path).first_raw()` instead")]
 pub fn mime_str_for_path_ext<P: AsRef<Path>>(path: P) -> Option<&'static str> {
     from_path(path).first_raw()
@@ -333,6 +1789,7 @@ pub fn mime_str_for_path_ext<P: AsRef<Path>>(path: P) -> Option<&'static str> {
 /// header at all instead of defaulting to `application/octet-stream`.
 ///
 /// [rfc7231]: https://tools.ietf.org/html/rfc7231#section-3.1.1.5
+#[cfg(not(feature = "no-deprecated"))]
 #[deprecated(
     since = "2.0.0",
     note = "use `from_ext(search_ext).first_or_octet_stream()` instead"
@@ -345,6 +1802,7 @@ pub fn get_mime_type(search_ext: &str) -> Mime {
 ///
 /// If there is no association for the extension, or `ext` is empty,
 /// `None` is returned.
+#[cfg(not(feature = "no-deprecated"))]
 #[deprecated(since = "2.0.0", note = "use `from_ext(search_ext).first()` instead")]
 pub fn get_mime_type_opt(search_ext: &str) -> Option<Mime> {
     from_ext(search_ext).first()
@@ -356,6 +1814,7 @@ pub fn get_mime_type_opt(search_ext: &str) -> Option<Mime> {
 /// it will be converted to lowercase to facilitate the search.
 ///
 /// Returns `None` if `search_ext` is empty or an associated extension was not found.
+#[cfg(not(feature = "no-deprecated"))]
 #[deprecated(
     since = "2.0.0",
     note = "use `from_ext(search_ext).first_raw()` instead"
@@ -370,6 +1829,10 @@ pub fn get_mime_type_str(search_ext: &str) -> Option<&'static str> {
 ///
 /// Returns `None` if the MIME type is unknown.
 ///
+/// Extensions are listed alphabetically, except that the type's preferred extension (see
+/// [`preferred_extension()`](fn.preferred_extension.html)), where one is curated, comes first:
+/// `image/jpeg` gives `["jpg", "jfif", "jpe", "jpeg"]`.
+///
 /// ### Wildcards
 /// If the top-level of the MIME type is a wildcard (`*`), returns all extensions.
 ///
@@ -379,6 +1842,120 @@ pub fn get_mime_extensions(mime: &Mime) -> Option<&'static [&'static str]> {
     get_extensions(mime.type_().as_ref(), mime.subtype().as_ref())
 }
 
+/// Get the number of known extensions for a given `Mime`.
+///
+/// This follows the same rules as [`get_mime_extensions()`](fn.get_mime_extensions.html),
+/// including wildcards, and returns `0` if the MIME type is unknown. No allocation is performed.
+#[cfg(feature = "rev-mappings")]
+pub fn get_mime_extensions_count(mime: &Mime) -> usize {
+    get_mime_extensions(mime).map_or(0, <[_]>::len)
+}
+
+/// Get an iterator over the known extensions for a given `Mime`.
+///
+/// This follows the same rules as [`get_mime_extensions()`](fn.get_mime_extensions.html),
+/// including wildcards, but yields nothing if the MIME type is unknown instead of returning
+/// `None`.
+#[cfg(feature = "rev-mappings")]
+pub fn mime_extensions(mime: &Mime) -> impl Iterator<Item = &'static str> {
+    get_mime_extensions(mime).unwrap_or(&[]).iter().cloned()
+}
+
+/// Get the single extension which should be preferred for a given `Mime`, e.g. when naming a
+/// file: `jpg` for `image/jpeg` rather than `jpeg` or `jpe`.
+///
+/// Ignores parameters. Case-insensitive. The preferred extension is taken from a short curated
+/// list for common types; for other types it is the first known extension which is not an
+/// alternative spelling of another (see [`resolve_with_alias()`](fn.resolve_with_alias.html)).
+///
+/// Returns `None` if the MIME type is unknown, or if either level is a wildcard.
+#[cfg(feature = "rev-mappings")]
+pub fn preferred_extension(mime: &Mime) -> Option<&'static str> {
+    if mime.type_() == mime::STAR || mime.subtype() == mime::STAR {
+        return None;
+    }
+
+    let exts = get_mime_extensions(mime)?;
+
+    preferred_exts::get_preferred(essence_str(mime.as_ref()))
+        .or_else(|| {
+            exts.iter()
+                .cloned()
+                .find(|ext| ext_aliases::get_canonical(ext).is_none())
+        })
+        .or_else(|| exts.first().cloned())
+}
+
+/// Get an iterator over `(extension, media type)` pairs for every known extension matching the
+/// glob `pattern`, e.g. `*ml` for `html`, `xml`, `yaml` and so on.
+///
+/// In `pattern`, `*` matches any sequence of characters (including none) and `?` matches any
+/// single character; everything else matches itself, case-insensitively. Extensions are yielded
+/// as stored in the crate's table, each once per media type it maps to; the order is
+/// unspecified.
+#[cfg(feature = "rev-mappings")]
+pub fn mimes_for_extension_glob(
+    pattern: &str,
+) -> impl Iterator<Item = (&'static str, &'static str)> + '_ {
+    impl_::entries()
+        .filter(move |&(ext, _)| glob_matches(pattern, ext))
+        .flat_map(|(ext, mimes)| mimes.iter().map(move |&mime| (ext, mime)))
+}
+
+/// `true` if `text` matches the glob `pattern` (`*` and `?` only), ignoring ASCII case.
+#[cfg(feature = "rev-mappings")]
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // position of the last `*` in `pattern`, and of `text` when it was reached
+    let mut star = None;
+
+    while t < text.len() {
+        // `*` must be checked first, or it would be consumed as a literal wherever `text` has
+        // a `*` too and no backtrack point would be recorded
+        if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if p < pattern.len()
+            && (pattern[p] == '?' || pattern[p].eq_ignore_ascii_case(&text[t]))
+        {
+            p += 1;
+            t += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // let the last `*` match one more character and retry
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Get an iterator over every known extension with a media type ending in the structured
+/// syntax suffix `+<suffix>`, e.g. `svg` (for `image/svg+xml`) for the suffix `xml`.
+///
+/// `suffix` is given without the `+` and matched case-insensitively. Extensions are yielded as
+/// stored in the crate's table, each once; the order is unspecified.
+#[cfg(feature = "rev-mappings")]
+pub fn extensions_for_suffix(suffix: &str) -> impl Iterator<Item = &'static str> + '_ {
+    impl_::entries()
+        .filter(move |&(_, mimes)| {
+            mimes.iter().any(|mime| {
+                let subtype = essence_str(mime).rsplit('/').next().unwrap_or("");
+                match subtype.rfind('+') {
+                    Some(plus) => subtype[plus + 1..].eq_ignore_ascii_case(suffix),
+                    None => false,
+                }
+            })
+        })
+        .map(|(ext, _)| ext)
+}
+
 /// Get a list of known extensions for a MIME type string.
 ///
 /// Ignores parameters (only searches `<main type>/<subtype>`). Case-insensitive.
@@ -390,8 +1967,11 @@ pub fn get_mime_extensions(mime: &Mime) -> Option<&'static [&'static str]> {
 ///
 /// If the sub-level of the MIME type is a wildcard, returns all extensions for the top-level.
 ///
-/// ### Panics
-/// If `mime_str` is not a valid MIME type specifier (naive).
+/// ### Malformed Input
+/// Never panics: returns `None` if `mime_str` does not contain a `/`, or if its top-level or
+/// sub-level is empty (e.g. `"/"` or `"*/"`). See
+/// [`get_mime_extensions_lenient()`](fn.get_mime_extensions_lenient.html) for a version which
+/// accepts more.
 #[cfg(feature = "rev-mappings")]
 pub fn get_mime_extensions_str(mut mime_str: &str) -> Option<&'static [&'static str]> {
     mime_str = mime_str.trim();
@@ -405,7 +1985,105 @@ pub fn get_mime_extensions_str(mut mime_str: &str) -> Option<&'static [&'static
         (&mime_str[..split_idx], &mime_str[split_idx + 1..])
     };
 
-    get_extensions(top, sub)
+    if top.is_empty() || sub.is_empty() {
+        return None;
+    }
+
+    get_extensions(top, sub)
+}
+
+/// `true` if `ext` is among the known extensions for `mime`, compared case-insensitively.
+///
+/// This follows the same rules as [`get_mime_extensions()`](fn.get_mime_extensions.html),
+/// including wildcards, so every known extension matches `*/*`.
+#[cfg(feature = "rev-mappings")]
+pub fn extension_matches(mime: &Mime, ext: &str) -> bool {
+    mime_extensions(mime).any(|known| known.eq_ignore_ascii_case(ext))
+}
+
+/// Get the known extensions for the media ranges accepted by an HTTP `Accept` header, most
+/// preferred first.
+///
+/// Each comma-separated range (including wildcards like `image/*`) is resolved as by
+/// [`get_mime_extensions_str()`](fn.get_mime_extensions_str.html), and extensions are ordered
+/// by the descending quality (`q`) of the range they came from, then by the order of the
+/// ranges in the header. Each extension appears once, at its best position.
+///
+/// A missing or malformed `q` value counts as `1`. Ranges with `q=0` are explicitly not
+/// acceptable, so their extensions are left out even if another range matches them. Ranges
+/// which cannot be resolved are ignored.
+#[cfg(feature = "rev-mappings")]
+pub fn extensions_for_accept(accept: &str) -> Vec<&'static str> {
+    let mut ranges: Vec<(f32, &str)> = accept
+        .split(',')
+        .map(|range| {
+            let q = range
+                .split(';')
+                .skip(1)
+                .filter_map(|param| {
+                    let mut kv = param.splitn(2, '=');
+                    match (kv.next(), kv.next()) {
+                        (Some(k), Some(v)) if k.trim().eq_ignore_ascii_case("q") => Some(v),
+                        _ => None,
+                    }
+                })
+                .next()
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .filter(|q| *q >= 0.0 && *q <= 1.0)
+                .unwrap_or(1.0);
+
+            (q, range)
+        })
+        .collect();
+
+    // stable, so ranges of equal quality keep their order in the header
+    ranges.sort_by(|&(a, _), &(b, _)| b.partial_cmp(&a).unwrap_or(std::cmp::Ordering::Equal));
+
+    let rejected: BTreeSet<&str> = ranges
+        .iter()
+        .filter(|&&(q, _)| q == 0.0)
+        .filter_map(|&(_, range)| get_mime_extensions_str(range))
+        .flat_map(|exts| exts.iter().cloned())
+        .collect();
+
+    let mut exts = Vec::new();
+    let mut seen = BTreeSet::new();
+
+    for &(_, range) in ranges.iter().filter(|&&(q, _)| q > 0.0) {
+        for &ext in get_mime_extensions_str(range).unwrap_or(&[]) {
+            if !rejected.contains(ext) && seen.insert(ext) {
+                exts.push(ext);
+            }
+        }
+    }
+
+    exts
+}
+
+/// Get a list of known extensions for a MIME type string, tolerating sloppy input.
+///
+/// Like [`get_mime_extensions_str()`](fn.get_mime_extensions_str.html), parameters are
+/// ignored and the search is case-insensitive, so this gives the same result as
+/// [`get_mime_extensions()`](fn.get_mime_extensions.html) with the parsed `Mime`. In addition:
+///
+/// * whitespace around the top-level and sub-level is ignored (`" text / html ; q=0.5"`);
+/// * a missing sub-level is a wildcard, so `image` and `image/` are the same as `image/*`.
+///
+/// Returns `None` if the MIME type is unknown or the top-level is empty. Never panics.
+#[cfg(feature = "rev-mappings")]
+pub fn get_mime_extensions_lenient(mime_str: &str) -> Option<&'static [&'static str]> {
+    let essence = essence_str(mime_str);
+
+    let (top, sub) = match essence.find('/') {
+        Some(split_idx) => (essence[..split_idx].trim(), essence[split_idx + 1..].trim()),
+        None => (essence, ""),
+    };
+
+    if top.is_empty() {
+        return None;
+    }
+
+    get_extensions(top, if sub.is_empty() { "*" } else { sub })
 }
 
 /// Get the extensions for a given top-level and sub-level of a MIME type
@@ -423,6 +2101,7 @@ pub fn get_extensions(toplevel: &str, sublevel: &str) -> Option<&'static [&'stat
 }
 
 /// Get the MIME type for `application/octet-stream` (generic binary stream)
+#[cfg(not(feature = "no-deprecated"))]
 #[deprecated(since = "2.0.0", note = "use `mime::APPLICATION_OCTET_STREAM` instead")]
 pub fn octet_stream() -> Mime {
     "application/octet-stream".parse().unwrap()
@@ -432,11 +2111,21 @@ pub fn octet_stream() -> Mime {
 mod tests {
     include!("mime_types.rs");
 
-    use super::{expect_mime, from_ext, from_path, get_mime_extensions_str};
+    #[cfg(feature = "path")]
+    use super::from_path;
+    use super::{
+        all_extensions, all_interpretations, canonical_extension, display_extension, expect_mime,
+        from_ext, get_mime_extensions_str, is_ambiguous_ext, resolve_with_alias, write_mime_types,
+        CaseStyle, GuessError, MimeGuess, MimeGuessBuilder, MimeRegistry, OwnedMimeGuess,
+        MAX_EXTENSION_LEN,
+    };
     #[allow(deprecated, unused_imports)]
     use std::ascii::AsciiExt;
 
+    use std::collections::BTreeSet;
     use std::fmt::Debug;
+    use std::mem;
+    #[cfg(feature = "path")]
     use std::path::Path;
 
     #[test]
@@ -463,18 +2152,21 @@ mod tests {
             "application/octet-stream".to_string()
         );
 
-        assert_eq!(
-            from_path(Path::new("/path/to/file.gif"))
-                .first_or_octet_stream()
-                .to_string(),
-            "image/gif".to_string()
-        );
-        assert_eq!(
-            from_path("/path/to/file.gif")
-                .first_or_octet_stream()
-                .to_string(),
-            "image/gif".to_string()
-        );
+        #[cfg(feature = "path")]
+        {
+            assert_eq!(
+                from_path(Path::new("/path/to/file.gif"))
+                    .first_or_octet_stream()
+                    .to_string(),
+                "image/gif".to_string()
+            );
+            assert_eq!(
+                from_path("/path/to/file.gif")
+                    .first_or_octet_stream()
+                    .to_string(),
+                "image/gif".to_string()
+            );
+        }
     }
 
     #[test]
@@ -489,46 +2181,1935 @@ mod tests {
         );
         assert_eq!(from_ext("blahblah").first(), None);
 
+        #[cfg(feature = "path")]
+        {
+            assert_eq!(
+                from_path("/path/to/file.gif").first().unwrap().to_string(),
+                "image/gif".to_string()
+            );
+            assert_eq!(from_path("/path/to/file").first(), None);
+        }
+    }
+
+    // run on `wasm32-wasip1` in CI, with and without the `path` feature
+    #[test]
+    fn test_from_ext_smoke() {
+        assert_eq!(from_ext("json").first(), Some(mime::APPLICATION_JSON));
+        assert_eq!(from_ext("JSON"), from_ext("json"));
+    }
+
+    #[test]
+    fn test_enumerate() {
+        let guess = from_ext("gz");
+
+        let mut enumerated = guess.enumerate_raw();
+        assert_eq!(enumerated.next(), Some((0, guess.first_raw().unwrap())));
+        assert_eq!(enumerated.next().map(|(i, _)| i), Some(1));
+
+        assert_eq!(guess.enumerate().next(), Some((0, guess.first().unwrap())));
+        assert_eq!(guess.enumerate().count(), guess.count());
+    }
+
+    #[test]
+    fn test_from_ext_interned() {
+        let (lower, lower_guess) = MimeGuess::from_ext_interned("png");
+        let (upper, upper_guess) = MimeGuess::from_ext_interned("PnG");
+
+        assert_eq!(lower, Some("png"));
+        assert_eq!(lower_guess, from_ext("png"));
+        assert_eq!(lower_guess, upper_guess);
+        assert!(std::ptr::eq(lower.unwrap(), upper.unwrap()));
+        assert_eq!(canonical_extension("PNG"), lower);
+
         assert_eq!(
-            from_path("/path/to/file.gif").first().unwrap().to_string(),
-            "image/gif".to_string()
+            MimeGuess::from_ext_interned("blahblah"),
+            (None, from_ext(""))
         );
-        assert_eq!(from_path("/path/to/file").first(), None);
+        assert_eq!(canonical_extension(""), None);
     }
 
     #[test]
-    fn test_are_mime_types_parseable() {
-        for (_, mimes) in MIME_TYPES {
-            mimes.iter().for_each(|s| {
-                expect_mime(s);
-            });
+    fn test_resolve_with_alias() {
+        assert_eq!(resolve_with_alias("jpe"), (Some("jpg"), from_ext("jpg")));
+        assert_eq!(resolve_with_alias("JPEG"), (Some("jpg"), from_ext("jpg")));
+        assert_eq!(resolve_with_alias("jpg"), (Some("jpg"), from_ext("jpg")));
+        assert_eq!(resolve_with_alias("PNG"), (Some("png"), from_ext("png")));
+        assert_eq!(resolve_with_alias("blahblah"), (None, from_ext("")));
+    }
+
+    #[test]
+    fn test_ext_aliases() {
+        use super::ext_aliases::EXT_ALIASES;
+
+        for (&(alias, _), &(n_alias, _)) in EXT_ALIASES.iter().zip(EXT_ALIASES.iter().skip(1)) {
+            assert!(
+                alias < n_alias,
+                "EXT_ALIASES not sorted: {:?} < {:?}",
+                alias,
+                n_alias
+            );
+        }
+
+        for &(alias, canonical) in EXT_ALIASES {
+            assert!(!from_ext(alias).is_empty(), "unknown alias {:?}", alias);
+            assert_eq!(
+                from_ext(alias),
+                from_ext(canonical),
+                "{:?} -> {:?}",
+                alias,
+                canonical
+            );
         }
     }
 
-    // RFC: Is this test necessary anymore? --@cybergeek94, 2/1/2016
     #[test]
-    fn test_are_extensions_ascii() {
-        for (ext, _) in MIME_TYPES {
-            assert!(ext.is_ascii(), "Extension not ASCII: {:?}", ext);
+    fn test_display_extension() {
+        assert_eq!(display_extension("png", CaseStyle::Upper).unwrap(), "PNG");
+        assert_eq!(display_extension("pNg", CaseStyle::Lower).unwrap(), "png");
+        assert_eq!(
+            display_extension("PNG", CaseStyle::AsStored).unwrap(),
+            "png"
+        );
+        assert_eq!(display_extension("blahblah", CaseStyle::Upper), None);
+    }
+
+    #[test]
+    fn test_font_types() {
+        for &(ext, font_type) in &[
+            ("woff", "font/woff"),
+            ("woff2", "font/woff2"),
+            ("ttf", "font/ttf"),
+            ("otf", "font/otf"),
+            ("ttc", "font/collection"),
+        ] {
+            let guess = from_ext(ext);
+            assert_eq!(guess.first_raw(), Some(font_type), "{:?}", ext);
+            assert!(guess.is_font(), "{:?}", ext);
         }
+
+        assert!(from_ext("eot").is_font());
+        assert!(!from_ext("png").is_font());
+        assert!(!from_ext("blahblah").is_font());
+
+        assert!(MimeGuess::from_static(&["Font/WOFF2"]).is_font());
+        assert!(MimeGuess::from_static(&["APPLICATION/X-FONT-TTF; charset=binary"]).is_font());
+        assert!(!MimeGuess::from_static(&["font"]).is_font());
+        assert!(!MimeGuess::from_static(&["application/fonts"]).is_font());
     }
 
     #[test]
-    fn test_are_extensions_sorted() {
-        // simultaneously checks the requirement that duplicate extension entries are adjacent
-        for (&(ext, _), &(n_ext, _)) in MIME_TYPES.iter().zip(MIME_TYPES.iter().skip(1)) {
+    fn test_iter_sorted_by_top_level() {
+        let sorted: Vec<String> = from_ext("ttf")
+            .iter_sorted_by_top_level(&["Application", "image"])
+            .map(|mime| mime.to_string())
+            .collect();
+
+        assert_eq!(
+            sorted,
+            [
+                "application/x-font-ttf",
+                "application/font-sfnt",
+                "font/ttf"
+            ]
+        );
+
+        let sorted: Vec<_> = from_ext("mp2")
+            .iter_sorted_by_top_level(&["video"])
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                "video/mpeg".parse::<super::Mime>().unwrap(),
+                "audio/mpeg".parse().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recommend_nosniff() {
+        assert!(!from_ext("png").recommend_nosniff());
+        assert!(from_ext("ttf").recommend_nosniff());
+        assert!(from_ext("blahblah").recommend_nosniff());
+    }
+
+    #[test]
+    fn test_modern_web_types() {
+        for &(ext, first) in &[
+            ("wasm", "application/wasm"),
+            ("map", "application/json"),
+            ("js", "text/javascript"),
+            ("mjs", "text/javascript"),
+            ("cjs", "text/javascript"),
+        ] {
+            assert_eq!(from_ext(ext).first_raw(), Some(first), "{:?}", ext);
+        }
+    }
+
+    #[test]
+    fn test_guessable() {
+        use super::Guessable;
+        use std::ffi::OsStr;
+
+        fn guess_first<G: Guessable>(input: G) -> Option<&'static str> {
+            input.guess().first_raw()
+        }
+
+        #[cfg(feature = "path")]
+        {
+            assert_eq!(
+                guess_first(Path::new("/path/to/file.gif")),
+                Some("image/gif")
+            );
+        }
+        assert_eq!(guess_first("gif"), Some("image/gif"));
+        assert_eq!(guess_first(OsStr::new("gif")), Some("image/gif"));
+        assert_eq!(guess_first(&mime::IMAGE_GIF), Some("image/gif"));
+        assert_eq!(guess_first(mime::TEXT_PLAIN_UTF_8), Some("text/plain"));
+
+        let font_sfnt: super::Mime = "application/font-sfnt".parse().unwrap();
+        assert_eq!(font_sfnt.guess().count(), 1);
+        assert_eq!(guess_first(&font_sfnt), Some("application/font-sfnt"));
+
+        let unknown: super::Mime = "application/x-blahblah".parse().unwrap();
+        assert!(unknown.guess().is_empty());
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let mjs = from_ext("mjs");
+        assert_eq!(mjs.count(), 2);
+        assert_eq!(
+            mjs.canonicalize().iter_raw().collect::<Vec<_>>(),
+            ["text/javascript"]
+        );
+
+        let ttf = from_ext("ttf").canonicalize();
+        assert_eq!(
+            ttf.iter_raw().collect::<Vec<_>>(),
+            ["font/ttf", "font/sfnt"]
+        );
+
+        let png = from_ext("png");
+        assert_eq!(png.canonicalize(), super::OwnedMimeGuess::from(png));
+        assert!(from_ext("blahblah").canonicalize().is_empty());
+    }
+
+    #[test]
+    fn test_mime_aliases() {
+        use super::mime_aliases::MIME_ALIASES;
+
+        for (&(old, _), &(n_old, _)) in MIME_ALIASES.iter().zip(MIME_ALIASES.iter().skip(1)) {
             assert!(
-                ext <= n_ext,
-                "Extensions in src/mime_types should be sorted lexicographically
-                in ascending order. Failed assert: {:?} <= {:?}",
-                ext,
-                n_ext
+                old < n_old,
+                "MIME_ALIASES not sorted: {:?} < {:?}",
+                old,
+                n_old
             );
         }
+
+        for &(old, current) in MIME_ALIASES {
+            expect_mime(old);
+            expect_mime(current);
+            assert_eq!(super::mime_aliases::get_current(current), None, "{:?}", old);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "path")]
+    fn test_from_path_dot_components() {
+        assert_eq!(from_path("./dir/../file.png"), from_ext("png"));
+        assert_eq!(from_path("a/./b.txt"), from_ext("txt"));
+        assert_eq!(from_path("../b.txt"), from_ext("txt"));
+
+        assert!(from_path("foo/..").is_empty());
+        assert!(from_path("foo.png/..").is_empty());
+        assert!(from_path("foo.png/.").is_empty());
+        assert!(from_path("..").is_empty());
+        assert!(from_path(".").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "path")]
+    fn test_from_path_dotfiles() {
+        assert!(from_path(".gitignore").is_empty());
+        assert!(from_path("/home/user/.bashrc").is_empty());
+        assert_eq!(from_path(".config.json"), from_ext("json"));
+        assert_eq!(from_path("dir/.hidden.png"), from_ext("png"));
+
+        assert!(from_path("archive.").is_empty());
+        assert!(from_path("archive.gz.").is_empty());
+        assert_eq!(from_path("archive..gz"), from_ext("gz"));
+        assert_eq!(from_path("..gz"), from_ext("gz"));
+        assert_eq!(from_path("dir/..gz"), from_ext("gz"));
+    }
+
+    #[test]
+    fn test_multipart_with_boundary() {
+        use super::multipart_with_boundary;
+
+        let form_data = multipart_with_boundary("form-data", "xyz").unwrap();
+        assert_eq!(form_data.essence_str(), "multipart/form-data");
+        assert_eq!(form_data.get_param(mime::BOUNDARY).unwrap(), "xyz");
+        assert_eq!(form_data.to_string(), "multipart/form-data; boundary=xyz");
+
+        let quoted = multipart_with_boundary("mixed", "simple boundary").unwrap();
+        assert_eq!(quoted.get_param(mime::BOUNDARY).unwrap(), "simple boundary");
+
+        assert_eq!(multipart_with_boundary("form-data", ""), None);
+        assert_eq!(multipart_with_boundary("form-data", "trailing "), None);
+        assert_eq!(multipart_with_boundary("form-data", "semi;colon"), None);
+        assert_eq!(multipart_with_boundary("form-data", &"x".repeat(71)), None);
+        assert_eq!(multipart_with_boundary("", "xyz"), None);
+    }
+
+    #[test]
+    fn test_first_with_default_charset() {
+        let txt = from_ext("txt").first_with_default_charset().unwrap();
+        assert_eq!(txt, mime::TEXT_PLAIN_UTF_8);
+
+        let asc = from_ext("asc").first_with_default_charset().unwrap();
+        assert_eq!(
+            asc.to_string(),
+            "application/pgp-signature; charset=us-ascii"
+        );
+
+        assert_eq!(
+            from_ext("png").first_with_default_charset(),
+            Some(mime::IMAGE_PNG)
+        );
+        assert_eq!(from_ext("blahblah").first_with_default_charset(), None);
+    }
+
+    #[test]
+    fn test_preferred() {
+        assert_eq!(from_ext("txt").preferred(), Some(mime::TEXT_PLAIN_UTF_8));
+        assert_eq!(
+            from_ext("json").preferred().unwrap().to_string(),
+            "application/json; charset=utf-8"
+        );
+        assert_eq!(
+            from_ext("svg").preferred().unwrap().to_string(),
+            "image/svg+xml; charset=utf-8"
+        );
+        assert_eq!(from_ext("png").preferred(), Some(mime::IMAGE_PNG));
+        assert_eq!(from_ext("blahblah").preferred(), None);
+
+        assert_eq!(
+            from_ext("csv")
+                .preferred_with_charset("iso-8859-1")
+                .unwrap()
+                .to_string(),
+            "text/csv; charset=iso-8859-1"
+        );
+        assert_eq!(
+            from_ext("png").preferred_with_charset("iso-8859-1"),
+            Some(mime::IMAGE_PNG)
+        );
+
+        for &charset in &["", "utf 8", "utf-8; q=1", "\"utf-8\"", "utf-8\n", "åäö"] {
+            assert_eq!(
+                from_ext("txt").preferred_with_charset(charset),
+                None,
+                "{:?}",
+                charset
+            );
+            assert_eq!(
+                from_ext("png").preferred_with_charset(charset),
+                None,
+                "{:?}",
+                charset
+            );
+        }
+    }
+
+    #[test]
+    fn test_implied_charset() {
+        assert_eq!(from_ext("html").implied_charset(), Some("utf-8"));
+        assert_eq!(from_ext("json").implied_charset(), Some("utf-8"));
+        assert_eq!(from_ext("svg").implied_charset(), Some("utf-8"));
+        assert_eq!(from_ext("asc").implied_charset(), Some("us-ascii"));
+        // not in the table, but still `text/*`
+        assert_eq!(from_ext("rtx").implied_charset(), Some("utf-8"));
+        assert_eq!(from_ext("png").implied_charset(), None);
+        assert_eq!(from_ext("blahblah").implied_charset(), None);
+    }
+
+    #[test]
+    fn test_iter_with_charsets() {
+        let txt: Vec<_> = from_ext("txt").iter_with_charsets().collect();
+        assert_eq!(txt, [(mime::TEXT_PLAIN, Some("utf-8"))]);
+
+        let map: Vec<_> = from_ext("map").iter_with_charsets().collect();
+        assert_eq!(
+            map,
+            [
+                (mime::APPLICATION_JSON, Some("utf-8")),
+                (mime::TEXT_PLAIN, Some("utf-8"))
+            ]
+        );
+
+        let png: Vec<_> = from_ext("png").iter_with_charsets().collect();
+        assert_eq!(png, [(mime::IMAGE_PNG, None)]);
+        assert_eq!(from_ext("blahblah").iter_with_charsets().count(), 0);
+    }
+
+    #[test]
+    fn test_default_charsets() {
+        use super::charsets::DEFAULT_CHARSETS;
+
+        for (&(mime, _), &(n_mime, _)) in
+            DEFAULT_CHARSETS.iter().zip(DEFAULT_CHARSETS.iter().skip(1))
+        {
+            assert!(
+                mime < n_mime,
+                "DEFAULT_CHARSETS not sorted: {:?} < {:?}",
+                mime,
+                n_mime
+            );
+        }
+    }
+
+    #[test]
+    fn test_multiple_types_order() {
+        assert_eq!(
+            from_ext("ttf").iter_raw().collect::<Vec<_>>(),
+            [
+                "font/ttf",
+                "application/x-font-ttf",
+                "application/font-sfnt"
+            ]
+        );
+        assert_eq!(
+            from_ext("gz").iter_raw().collect::<Vec<_>>(),
+            ["application/gzip", "application/x-gzip"]
+        );
+    }
+
+    #[test]
+    fn test_from_ext_sanitized() {
+        let png = from_ext("png");
+        assert_eq!(MimeGuess::from_ext_sanitized("png"), png);
+        assert_eq!(MimeGuess::from_ext_sanitized("\u{FEFF}png"), png);
+        assert_eq!(MimeGuess::from_ext_sanitized(" \u{200B}png\u{2060}\n"), png);
+
+        assert!(MimeGuess::from_ext_sanitized("p\u{200B}ng").is_empty());
+        assert!(MimeGuess::from_ext_sanitized("\u{FEFF}").is_empty());
+    }
+
+    #[test]
+    fn test_ext_equivalent() {
+        use super::ext_equivalent;
+
+        assert!(ext_equivalent("jpg", "jpeg"));
+        assert!(ext_equivalent("JPG", "jpe"));
+        assert!(ext_equivalent("png", "png"));
+        assert!(!ext_equivalent("jpg", "png"));
+        assert!(!ext_equivalent("md", "markdown"));
+        assert!(!ext_equivalent("blahblah", "blahblah"));
+    }
+
+    #[test]
+    #[cfg(feature = "path")]
+    // `env::temp_dir()` is unsupported on WASI
+    #[cfg_attr(target_os = "wasi", ignore)]
+    fn test_from_path_special() {
+        use std::{env, fs, process};
+
+        let dir = env::temp_dir().join(format!("mime_guess_test_special_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let file = dir.join("file.png");
+        fs::write(&file, b"").unwrap();
+
+        assert_eq!(
+            MimeGuess::from_path_special(&dir).unwrap().to_string(),
+            "inode/directory"
+        );
+        assert_eq!(MimeGuess::from_path_special(&file), Some(mime::IMAGE_PNG));
+
+        #[cfg(unix)]
+        {
+            let link = dir.join("link.png");
+            ::std::os::unix::fs::symlink(&file, &link).unwrap();
+            assert_eq!(
+                MimeGuess::from_path_special(&link).unwrap().to_string(),
+                "inode/symlink"
+            );
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        // falls back to the extension when the path doesn't exist
+        assert_eq!(MimeGuess::from_path_special(&file), Some(mime::IMAGE_PNG));
+    }
+
+    #[test]
+    fn test_best() {
+        assert_eq!(from_ext("png").best(), Some(mime::IMAGE_PNG));
+        assert_eq!(from_ext("ttf").best(), from_ext("ttf").first());
+        assert_eq!(from_ext("blahblah").best(), None);
+
+        let obsolete_first = MimeGuess(&["application/javascript", "text/javascript"]);
+        assert_eq!(obsolete_first.best(), Some(mime::TEXT_JAVASCRIPT));
+
+        let unregistered_first =
+            MimeGuess(&["application/x-parquet", "application/vnd.apache.parquet"]);
+        assert_eq!(
+            unregistered_first.best().unwrap().to_string(),
+            "application/vnd.apache.parquet"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "path")]
+    fn test_from_path_compound() {
+        for &(path, ext) in &[
+            ("archive.tar.gz", "tar.gz"),
+            ("/path/to/archive.TAR.BZ2", "tar.bz2"),
+            ("backup.2024.tar.xz", "tar.xz"),
+            ("archive.tar.zst", "tar.zst"),
+            ("foo.bar.gz", "gz"),
+            ("archive.tar", "tar"),
+            (".tar.gz", "gz"),
+        ] {
+            assert_eq!(
+                MimeGuess::from_path_compound(path),
+                from_ext(ext),
+                "{:?}",
+                path
+            );
+        }
+
+        assert_eq!(from_path("archive.tar.gz"), from_ext("gz"));
+        assert!(MimeGuess::from_path_compound("archive").is_empty());
+        assert!(MimeGuess::from_path_compound("archive.tar.gz/.").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "path")]
+    fn test_from_path_all_extensions() {
+        assert_eq!(MimeGuess::from_path_all_extensions("file"), None);
+        assert_eq!(MimeGuess::from_path_all_extensions(".bashrc"), None);
+        assert_eq!(MimeGuess::from_path_all_extensions("file.blahblah"), None);
+
+        assert_eq!(
+            MimeGuess::from_path_all_extensions("config.local.json"),
+            Some(("json", from_ext("json")))
+        );
+        assert_eq!(
+            MimeGuess::from_path_all_extensions(Path::new("/path/to/backup.2024.sql.gz")),
+            Some(("gz", from_ext("gz")))
+        );
+        assert_eq!(
+            MimeGuess::from_path_all_extensions("backup.2024.tar.gz"),
+            Some(("tar.gz", from_ext("tar.gz")))
+        );
+    }
+
+    #[test]
+    fn test_all_extensions() {
+        let exts: Vec<&str> = super::all_extensions().collect();
+        assert_eq!(exts.len(), MIME_TYPES.len());
+
+        for ext in &["gif", "txt", "json"] {
+            assert!(exts.contains(ext), "{:?}", ext);
+        }
+    }
+
+    #[test]
+    fn test_all_mime_types() {
+        let mimes: Vec<&str> = super::all_mime_types().collect();
+
+        assert!(mimes.contains(&"image/gif"));
+        assert!(mimes.contains(&"text/plain"));
+
+        for (mime, n_mime) in mimes.iter().zip(mimes.iter().skip(1)) {
+            assert!(mime < n_mime, "not deduplicated: {:?}, {:?}", mime, n_mime);
+        }
+    }
+
+    #[test]
+    fn test_table_stats() {
+        let stats = super::table_stats();
+
+        assert_eq!(stats.extensions, super::all_extensions().count());
+        assert_eq!(stats.mime_types, super::all_mime_types().count());
+        assert!(stats.extensions > 0 && stats.mime_types > 0);
+        // every extension is at least one byte and maps to at least one pointer-sized slice
+        assert!(stats.bytes > stats.extensions * (1 + mem::size_of::<&str>()));
+    }
+
+    #[test]
+    fn test_contains() {
+        let gz = from_ext("gz");
+        assert!(gz.contains_str("application/x-gzip"));
+        assert!(gz.contains_str("Application/GZIP; foo=bar"));
+        assert!(!gz.contains_str("application/zip"));
+
+        let md = from_ext("md");
+        assert!(md.contains(&"text/markdown; charset=utf-8".parse().unwrap()));
+        assert!(!md.contains(&mime::TEXT_PLAIN));
+        assert!(!from_ext("blahblah").contains(&mime::TEXT_PLAIN));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip() {
+        use super::OwnedMimeGuess;
+        use serde_json;
+
+        let gz = from_ext("gz");
+        let json = serde_json::to_string(&gz).unwrap();
+        assert_eq!(json, r#"["application/gzip","application/x-gzip"]"#);
+
+        let owned: OwnedMimeGuess = serde_json::from_str(&json).unwrap();
+        assert_eq!(owned, OwnedMimeGuess::from(gz));
+        assert_eq!(serde_json::to_string(&owned).unwrap(), json);
+
+        assert!(serde_json::from_str::<OwnedMimeGuess>(r#"["not a mime"]"#).is_err());
+
+        let deduped: OwnedMimeGuess =
+            serde_json::from_str(r#"["image/png","image/gif","IMAGE/PNG"]"#).unwrap();
+        assert_eq!(
+            deduped.iter_raw().collect::<Vec<_>>(),
+            ["image/png", "image/gif"]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sniff")]
+    fn test_sniff_reader() {
+        use std::io::{Cursor, Read};
+
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+        let mut seekable = Cursor::new(&png[..]);
+        assert_eq!(
+            MimeGuess::sniff_reader(&mut seekable).unwrap(),
+            from_ext("png")
+        );
+        assert_eq!(seekable.position(), 0);
+
+        let mut unseekable = &png[..];
+        let (guess, prefix) = MimeGuess::sniff_reader_unseekable(&mut unseekable).unwrap();
+        assert_eq!(guess, from_ext("png"));
+        assert_eq!(prefix, &png[..]);
+        assert!(unseekable.is_empty());
+
+        let mut long = Cursor::new(vec![b'%'; 1024]);
+        long.get_mut()[..5].copy_from_slice(b"%PDF-");
+        let (guess, prefix) = MimeGuess::sniff_reader_unseekable(&mut long).unwrap();
+        assert_eq!(guess, from_ext("pdf"));
+        assert_eq!(prefix.len(), super::sniff::SNIFF_LEN);
+        assert_eq!(long.bytes().count(), 1024 - super::sniff::SNIFF_LEN);
+
+        let mut text = Cursor::new("just some text");
+        assert!(MimeGuess::sniff_reader(&mut text).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "sniff")]
+    fn zip_entry(name: &str, data: &[u8]) -> Vec<u8> {
+        // local file header: signature, version, flags, compression, time, date, CRC-32
+        let mut entry = b"PK\x03\x04\x14\0\0\0\0\0\0\0\0\0\0\0\0\0".to_vec();
+        // compressed and uncompressed sizes, name length, extra field length
+        entry.extend(&(data.len() as u32).to_le_bytes());
+        entry.extend(&(data.len() as u32).to_le_bytes());
+        entry.extend(&(name.len() as u16).to_le_bytes());
+        entry.extend(&[0, 0]);
+
+        entry.extend(name.as_bytes());
+        entry.extend(data);
+        entry
+    }
+
+    #[test]
+    #[cfg(feature = "sniff")]
+    fn test_sniff_bytes() {
+        let docx = [
+            zip_entry("[Content_Types].xml", b"<Types/>"),
+            zip_entry("word/document.xml", b"<document/>"),
+        ]
+        .concat();
+        let odt = zip_entry("mimetype", b"application/vnd.oasis.opendocument.text");
+        let zip = zip_entry("hello.txt", b"Hello, world!");
+
+        let samples: &[(&[u8], MimeGuess)] = &[
+            (b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR", from_ext("png")),
+            (b"\xFF\xD8\xFF\xE0\0\x10JFIF", MimeGuess(&["image/jpeg"])),
+            (b"GIF89a\x01\0\x01\0", from_ext("gif")),
+            (b"GIF87a\x01\0\x01\0", from_ext("gif")),
+            (b"%PDF-1.7\n", from_ext("pdf")),
+            (b"\x1F\x8B\x08\0", MimeGuess(&["application/gzip"])),
+            (&zip, from_ext("zip")),
+            (&docx, from_ext("docx")),
+            (&odt, from_ext("odt")),
+        ];
+
+        for (i, &(bytes, expected)) in samples.iter().enumerate() {
+            assert_eq!(MimeGuess::sniff_bytes(bytes), expected, "sample {}", i);
+
+            // truncated inputs must never panic, and can only lose specificity
+            for len in 0..bytes.len() {
+                let guess = MimeGuess::sniff_bytes(&bytes[..len]);
+                assert!(
+                    guess.is_empty() || guess == expected || guess == from_ext("zip"),
+                    "sample {} truncated to {} bytes: {:?}",
+                    i,
+                    len,
+                    guess
+                );
+            }
+        }
+
+        // sizes near `u32::MAX` must not overflow offsets, even on 32-bit targets
+        for &name in &["mimetype", "hello.txt"] {
+            let mut huge = zip_entry(name, b"data");
+            huge[18..22].copy_from_slice(&u32::MAX.to_le_bytes());
+            assert_eq!(MimeGuess::sniff_bytes(&huge), from_ext("zip"), "{:?}", name);
+        }
+
+        let unknown_mimetype = zip_entry("mimetype", b"application/x-blahblah");
+        assert_eq!(MimeGuess::sniff_bytes(&unknown_mimetype), from_ext("zip"));
+
+        assert!(MimeGuess::sniff_bytes(b"").is_empty());
+        assert!(MimeGuess::sniff_bytes(b"just some text").is_empty());
+    }
+
+    #[test]
+    #[cfg(all(feature = "path", feature = "sniff"))]
+    fn test_from_path_and_content() {
+        use super::OwnedMimeGuess;
+
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        let guess = |path: &str, bytes: &[u8]| {
+            let mut reader = bytes;
+            MimeGuess::from_path_and_content(path, &mut reader).unwrap()
+        };
+
+        // agreement
+        assert_eq!(
+            guess("image.png", png),
+            OwnedMimeGuess::from(from_ext("png"))
+        );
+        // extension only
+        assert_eq!(
+            guess("notes.txt", b"hello"),
+            OwnedMimeGuess::from(from_ext("txt"))
+        );
+        // content only
+        assert_eq!(guess("image", png), OwnedMimeGuess::from(from_ext("png")));
+        assert_eq!(
+            guess("image.bin", png),
+            OwnedMimeGuess::from(from_ext("png"))
+        );
+        assert!(guess("unknown", b"hello").is_empty());
+
+        // disagreement
+        let disagree = guess("image.gif", png);
+        assert_eq!(
+            disagree.iter_raw().collect::<Vec<_>>(),
+            ["image/png", "image/gif"]
+        );
+    }
+
+    #[test]
+    fn test_tracked_guess() {
+        let gif = MimeGuess::from_ext_tracked("Gif");
+        assert_eq!(gif.matched_extension(), Some("gif"));
+        assert_eq!(gif.guess(), from_ext("gif"));
+        assert_eq!(MimeGuess::from(gif), from_ext("gif"));
+        assert!(MimeGuess::from_ext_tracked("").guess().is_empty());
+
+        #[cfg(feature = "path")]
+        {
+            let jpg = MimeGuess::from_path_tracked("a.JPG");
+            assert_eq!(jpg.matched_extension(), Some("jpg"));
+            assert_eq!(jpg.guess(), from_ext("jpg"));
+            assert_eq!(MimeGuess::from(jpg), from_path("a.JPG"));
+
+            assert_eq!(
+                MimeGuess::from_path_tracked("a.blahblah").matched_extension(),
+                None
+            );
+            assert_eq!(MimeGuess::from_path_tracked("a").matched_extension(), None);
+        }
+    }
+
+    #[test]
+    fn test_from_ext_case_sensitive() {
+        assert_eq!(MimeGuess::from_ext_case_sensitive("gif"), from_ext("gif"));
+        assert_eq!(
+            MimeGuess::from_ext_case_sensitive("gif").first(),
+            Some(mime::IMAGE_GIF)
+        );
+        assert!(MimeGuess::from_ext_case_sensitive("GIF").is_empty());
+        assert!(MimeGuess::from_ext_case_sensitive("Gif").is_empty());
+        assert!(MimeGuess::from_ext_case_sensitive("").is_empty());
+
+        assert!(!from_ext("GIF").is_empty());
+    }
+
+    #[test]
+    fn test_from_ext_lenient() {
+        let gif = from_ext("gif");
+        assert_eq!(MimeGuess::from_ext_lenient("gif"), gif);
+        assert_eq!(MimeGuess::from_ext_lenient(".gif"), gif);
+        assert_eq!(MimeGuess::from_ext_lenient("*.GIF"), gif);
+
+        assert_eq!(MimeGuess::from_ext_lenient("tar.gz"), from_ext("tar.gz"));
+        assert_eq!(MimeGuess::from_ext_lenient(".tar.gz"), from_ext("tar.gz"));
+
+        assert!(MimeGuess::from_ext_lenient("").is_empty());
+        assert!(MimeGuess::from_ext_lenient(".").is_empty());
+        assert!(MimeGuess::from_ext_lenient("*.").is_empty());
+        assert!(MimeGuess::from_ext_lenient("..gif").is_empty());
+    }
+
+    #[test]
+    fn test_registry_override() {
+        let mut registry = MimeRegistry::new();
+        registry.insert("gif", "image/x-custom-gif".parse().unwrap());
+        registry.insert("GIF", "image/x-custom-gif".parse().unwrap());
+        registry.insert("Gif", mime::IMAGE_GIF);
+
+        let guess = registry.guess("gIf");
+        assert_eq!(
+            guess.iter_raw().collect::<Vec<_>>(),
+            ["image/x-custom-gif", "image/gif"]
+        );
+
+        registry.extend(vec![
+            ("qml", "text/x-qml".parse().unwrap()),
+            ("mjs", mime::APPLICATION_JAVASCRIPT),
+        ]);
+        assert_eq!(registry.guess("qml").first_raw(), Some("text/x-qml"));
+        assert_eq!(
+            registry.guess("MJS").first(),
+            Some(mime::APPLICATION_JAVASCRIPT)
+        );
+    }
+
+    #[test]
+    fn test_registry_fallthrough() {
+        let mut registry = MimeRegistry::new();
+        registry.insert("qml", "text/x-qml".parse().unwrap());
+
+        assert_eq!(registry.guess("png"), from_ext("png").into());
+        assert_eq!(registry.guess("PNG"), from_ext("png").into());
+        assert!(registry.guess("qmlx").is_empty());
+        assert!(MimeRegistry::new().guess("qml").is_empty());
+    }
+
+    #[test]
+    fn test_registry_long_and_non_ascii_keys() {
+        let long = "x".repeat(MAX_EXTENSION_LEN + 1);
+
+        let mut registry = MimeRegistry::empty();
+        registry.insert(&long, "text/x-long".parse().unwrap());
+        registry.insert("ÄÖ", "text/x-umlaut".parse().unwrap());
+
+        assert_eq!(
+            registry.guess(&long.to_uppercase()).first_raw(),
+            Some("text/x-long")
+        );
+        assert_eq!(registry.guess("äö").first_raw(), Some("text/x-umlaut"));
+
+        let owned = registry.guess("Äö").to_owned_guess();
+        assert_eq!(owned.first_raw(), Some("text/x-umlaut"));
+    }
+
+    const MIME_TYPES_SAMPLE: &str = "\
+# a small mime.types sample\r
+\r
+text/x-qml\t\tqml qmlproject\r
+application/x-empty\r
+image/x-custom-gif  gif   # override a built-in mapping\r
+text/x-also-qml qml\r
+";
+
+    #[test]
+    fn test_registry_from_mime_types_file() {
+        let registry = MimeRegistry::from_mime_types_file(MIME_TYPES_SAMPLE.as_bytes()).unwrap();
+
+        assert_eq!(
+            registry.guess("qml").iter_raw().collect::<Vec<_>>(),
+            ["text/x-qml", "text/x-also-qml"]
+        );
+        assert_eq!(registry.guess("QMLPROJECT").first_raw(), Some("text/x-qml"));
+        assert_eq!(
+            registry.guess("gif").iter_raw().collect::<Vec<_>>(),
+            ["image/x-custom-gif"]
+        );
+        assert_eq!(registry.guess("png"), from_ext("png").into());
+    }
+
+    #[test]
+    fn test_registry_load_mime_types_empty() {
+        let mut registry = MimeRegistry::empty();
+        registry
+            .load_mime_types(MIME_TYPES_SAMPLE.as_bytes())
+            .unwrap();
+
+        assert_eq!(registry.guess("qmlproject").count(), 1);
+        assert!(registry.guess("png").is_empty());
+
+        let err = registry
+            .load_mime_types("text/x-foo foo\nnot-a-mime bar\n".as_bytes())
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(registry.guess("foo").first_raw(), Some("text/x-foo"));
+        assert!(registry.guess("bar").is_empty());
+    }
+
+    #[test]
+    fn test_write_mime_types_round_trip() {
+        let mut out = Vec::new();
+        write_mime_types(&mut out).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert!(lines.windows(2).all(|w| w[0] < w[1]), "lines not sorted");
+        assert!(lines.contains(&"image/gif gif"));
+
+        let mut registry = MimeRegistry::empty();
+        registry.load_mime_types(out.as_bytes()).unwrap();
+
+        for ext in all_extensions() {
+            // `Mime` normalizes media types to lowercase
+            let expected: BTreeSet<_> = from_ext(ext)
+                .iter_raw()
+                .map(str::to_ascii_lowercase)
+                .collect();
+            let actual: BTreeSet<_> = registry.guess(ext).iter_raw().map(str::to_owned).collect();
+            assert_eq!(actual, expected, "mappings differ for {:?}", ext);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "path")]
+    fn test_from_url() {
+        let png = from_ext("png");
+        assert_eq!(
+            MimeGuess::from_url("https://host/img/pic.png?v=3#frag"),
+            png
+        );
+        assert_eq!(
+            MimeGuess::from_url("https://host/img/pic.PNG#frag.gif"),
+            png
+        );
+        assert_eq!(MimeGuess::from_url("//host/pic.png"), png);
+        assert_eq!(MimeGuess::from_url("/img/pic.png?file=x.gif"), png);
+        assert_eq!(MimeGuess::from_url("pic.png"), png);
+        assert_eq!(MimeGuess::from_url("img//pic.png"), png);
+        assert_eq!(MimeGuess::from_url("a/b//c.png"), png);
+        assert_eq!(MimeGuess::from_url("a/b:c//d.png"), png);
+        assert_eq!(MimeGuess::from_url("file:///tmp/pic.png"), png);
+        assert_eq!(MimeGuess::from_url("git+ssh://host/pic.png"), png);
+        assert_eq!(MimeGuess::from_url("urn:pic.png"), png);
+
+        assert_eq!(
+            MimeGuess::from_url("http://host/index%2Ehtml"),
+            from_ext("html")
+        );
+        assert_eq!(
+            MimeGuess::from_url("http://host/my%20file.pdf"),
+            from_ext("pdf")
+        );
+
+        assert!(MimeGuess::from_url("https://host/img.png/").is_empty());
+        assert!(MimeGuess::from_url("https://host.png").is_empty());
+        assert!(MimeGuess::from_url("//host.png").is_empty());
+        assert!(MimeGuess::from_url("https://host.png?x.gif").is_empty());
+        assert!(MimeGuess::from_url("https://host/.htaccess").is_empty());
+        assert!(MimeGuess::from_url("https://host/pic%FF.png").is_empty());
+        assert!(MimeGuess::from_url("").is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            from_ext("gz").to_string(),
+            "application/gzip, application/x-gzip"
+        );
+        assert_eq!(from_ext("gif").to_string(), "image/gif");
+        assert_eq!(from_ext("blahblah").to_string(), "");
+    }
+
+    #[test]
+    fn test_owned_from_str_round_trip() {
+        for ext in &["ttf", "gz", "docm", "gif", "blahblah"] {
+            let guess = from_ext(ext);
+            let parsed: OwnedMimeGuess = guess.to_string().parse().unwrap();
+            assert_eq!(parsed, guess.into());
+            assert_eq!(parsed.to_string(), guess.to_string());
+        }
+
+        let parsed: OwnedMimeGuess = " text/x-qml,text/plain  ".parse().unwrap();
+        assert_eq!(
+            parsed.iter_raw().collect::<Vec<_>>(),
+            ["text/x-qml", "text/plain"]
+        );
+
+        assert!("image/png, not-a-mime".parse::<OwnedMimeGuess>().is_err());
+        assert!("image/png,,image/gif".parse::<OwnedMimeGuess>().is_err());
+        assert!(" ".parse::<OwnedMimeGuess>().unwrap().is_empty());
+
+        let parsed: OwnedMimeGuess = "image/png, IMAGE/PNG, image/gif".parse().unwrap();
+        assert_eq!(
+            parsed.iter_raw().collect::<Vec<_>>(),
+            ["image/png", "image/gif"]
+        );
+    }
+
+    #[test]
+    fn test_guess_ord_hash() {
+        use std::collections::HashSet;
+
+        let guesses = vec![
+            from_ext("png"),
+            from_ext("gz"),
+            from_ext("PNG"),
+            from_ext("blahblah"),
+            from_ext("gif"),
+        ];
+
+        let sorted: Vec<_> = guesses
+            .iter()
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                from_ext("blahblah"),
+                from_ext("gz"),
+                from_ext("gif"),
+                from_ext("png")
+            ]
+        );
+
+        let hashed: HashSet<_> = guesses.into_iter().collect();
+        assert_eq!(hashed.len(), 4);
+        assert!(hashed.contains(&from_ext("Png")));
+    }
+
+    #[test]
+    fn test_try_from_ext() {
+        assert_eq!(MimeGuess::try_from_ext("GIF"), Ok(from_ext("gif")));
+        assert_eq!(MimeGuess::try_from_ext(""), Err(GuessError::NoExtension));
+        assert_eq!(
+            MimeGuess::try_from_ext("blahblah"),
+            Err(GuessError::UnknownExtension)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "path")]
+    fn test_try_from_path() {
+        assert_eq!(MimeGuess::try_from_path("foo/bar.gif"), Ok(from_ext("gif")));
+        assert_eq!(
+            MimeGuess::try_from_path("foo/bar"),
+            Err(GuessError::NoExtension)
+        );
+        assert_eq!(
+            MimeGuess::try_from_path("foo.gif/."),
+            Err(GuessError::NoExtension)
+        );
+        assert_eq!(
+            MimeGuess::try_from_path("foo.blahblah"),
+            Err(GuessError::UnknownExtension)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[cfg(feature = "path")]
+    fn test_try_from_path_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"foo.gi\xFF"));
+        assert_eq!(
+            MimeGuess::try_from_path(path),
+            Err(GuessError::NonUtf8Extension)
+        );
+        assert_eq!(
+            GuessError::NonUtf8Extension.to_string(),
+            "file extension is not valid UTF-8"
+        );
+    }
+
+    #[cfg(all(feature = "path", unix))]
+    #[test]
+    fn test_from_path_dot_components_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = |bytes| Path::new(OsStr::from_bytes(bytes));
+
+        assert_eq!(from_path(path(b"caf\xE9/file.png")), from_ext("png"));
+        assert!(from_path(path(b"caf\xE9/file.png/.")).is_empty());
+        assert!(from_path(path(b"file\xE9.png/.")).is_empty());
+        assert!(from_path(path(b"caf\xE9.")).is_empty());
+        assert!(from_path(path(b"caf\xE9/.")).is_empty());
+    }
+
+    #[test]
+    fn test_from_os_ext() {
+        use std::ffi::{OsStr, OsString};
+        use std::path::Path;
+
+        assert_eq!(MimeGuess::from_os_ext(OsStr::new("GIF")), from_ext("gif"));
+        assert_eq!(
+            MimeGuess::from_os_ext(OsString::from("png")),
+            from_ext("png")
+        );
+        assert_eq!(
+            MimeGuess::from_os_ext(Path::new("a.tar.gz").extension().unwrap()),
+            from_ext("gz")
+        );
+        assert!(MimeGuess::from_os_ext("").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_os_ext_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        assert!(MimeGuess::from_os_ext(OsStr::from_bytes(b"gi\xFF")).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[cfg(feature = "path")]
+    fn test_from_path_lossy() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let latin1 = Path::new(OsStr::from_bytes(b"docs/caf\xE9.PDF"));
+        assert_eq!(MimeGuess::from_path_lossy(latin1), from_ext("pdf"));
+        assert_eq!(MimeGuess::from_path_lossy(latin1), from_path(latin1));
+
+        let bad_ext = Path::new(OsStr::from_bytes(b"file.pd\xE9f"));
+        assert!(MimeGuess::from_path_lossy(bad_ext).is_empty());
+
+        assert!(MimeGuess::from_path_lossy("file.pdf/.").is_empty());
+        assert_eq!(MimeGuess::from_path_lossy("file.gif"), from_ext("gif"));
+    }
+
+    #[test]
+    fn test_as_raw_slice() {
+        assert!(from_ext("html").as_raw_slice().contains(&"text/html"));
+        assert_eq!(
+            from_ext("gz").as_raw_slice().join(", "),
+            from_ext("gz").to_string()
+        );
+        assert!(from_ext("blahblah").as_raw_slice().is_empty());
+    }
+
+    #[test]
+    fn test_get() {
+        let gz = from_ext("gz");
+        assert_eq!(gz.get(0), gz.first());
+        assert_eq!(gz.get_raw(1), Some("application/x-gzip"));
+        assert_eq!(&gz[1], "application/x-gzip");
+        assert_eq!(gz.get(2), None);
+
+        let empty = from_ext("blahblah");
+        assert_eq!(empty.get(0), None);
+        assert_eq!(empty.get_raw(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let _ = &from_ext("gif")[1];
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let ttf = from_ext("ttf");
+        assert_eq!(ttf.to_vec().len(), ttf.count());
+        assert_eq!(ttf.to_vec(), ttf.iter().collect::<Vec<_>>());
+        assert_eq!(ttf.to_raw_vec(), ttf.as_raw_slice());
+        assert_eq!(ttf.to_raw_vec()[0], "font/ttf");
+
+        assert!(from_ext("blahblah").to_vec().is_empty());
+        assert!(from_ext("blahblah").to_raw_vec().is_empty());
+    }
+
+    #[test]
+    fn test_max_extension_len() {
+        for &(ext, _) in MIME_TYPES {
+            assert!(ext.len() <= MAX_EXTENSION_LEN, "{:?} too long", ext);
+        }
+
+        let huge = "a".repeat(1 << 20);
+        assert!(from_ext(&huge).is_empty());
+        #[cfg(feature = "path")]
+        assert!(from_path(format!("file.{}", huge)).is_empty());
+
+        let longest = "x".repeat(MAX_EXTENSION_LEN);
+        assert!(from_ext(&longest).is_empty());
+
+        // every entry point shares the length check, not just `from_ext()`
+        let too_long = format!("{:>width$}", "png", width = MAX_EXTENSION_LEN + 1);
+        assert_eq!(too_long.len(), 33);
+
+        assert!(from_ext(&too_long).is_empty());
+        assert!(MimeGuess::from_os_ext(&too_long).is_empty());
+        assert!(MimeGuess::from_ext_case_sensitive(&too_long).is_empty());
+        assert_eq!(
+            MimeGuess::from_ext_interned(&too_long),
+            (None, MimeGuess(&[]))
+        );
+        assert_eq!(MimeGuess::from_ext_tracked(&too_long).ext, None);
+        assert!(MimeGuess::from_ext_tracked(&too_long).guess.is_empty());
+        assert!(MimeGuess::from_ext_lenient(&format!(".{}", too_long)).is_empty());
+        assert!(MimeGuess::from_path_bytes(format!("file.{}", too_long).as_bytes()).is_empty());
+
+        #[cfg(feature = "path")]
+        {
+            let path = format!("file.{}", too_long);
+            assert!(MimeGuess::from_path(&path).is_empty());
+            assert!(MimeGuess::from_path_tracked(&path).guess.is_empty());
+            assert!(MimeGuess::from_path_lossy(&path).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_from_static() {
+        const GZ: MimeGuess = MimeGuess::from_static(&["application/gzip", "application/x-gzip"]);
+        const EMPTY: MimeGuess = MimeGuess::from_static(&[]);
+
+        assert_eq!(GZ, from_ext("gz"));
+        assert_eq!(GZ.first(), Some("application/gzip".parse().unwrap()));
+        assert!(EMPTY.is_empty());
+    }
+
+    #[test]
+    fn test_iter_clone_peek() {
+        let ttf = from_ext("ttf");
+
+        let mut iter = ttf.iter();
+        assert_eq!(iter.peek(), ttf.first());
+        iter.next();
+
+        let saved = iter.clone();
+        assert_eq!(iter.peek(), ttf.get(1));
+        assert_eq!(iter.next(), ttf.get(1));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(saved.collect::<Vec<_>>(), ttf.to_vec()[1..]);
+
+        let mut raw = ttf.iter_raw();
+        raw.next_back();
+        let saved = raw.clone();
+        assert_eq!(raw.by_ref().peekable().peek(), Some(&"font/ttf"));
+        assert_eq!(saved.peek(), Some("font/ttf"));
+        assert_eq!(saved.collect::<Vec<_>>(), ttf.as_raw_slice()[..2]);
+
+        assert_eq!(from_ext("blahblah").iter().peek(), None);
+        assert_eq!(from_ext("blahblah").iter_raw().peek(), None);
+    }
+
+    #[test]
+    fn test_owned_conversions() {
+        let gz = from_ext("gz");
+        let owned = OwnedMimeGuess::from(gz);
+        assert_eq!(owned.count(), gz.count());
+        assert_eq!(owned.first(), gz.first());
+        assert_eq!(owned.iter().collect::<Vec<_>>(), gz.to_vec());
+        assert_eq!(owned.to_static_guess(), Some(gz));
+
+        let from_strs = OwnedMimeGuess::from_strs(&["application/gzip", "application/x-gzip"]);
+        assert_eq!(from_strs.unwrap(), owned);
+
+        let custom = OwnedMimeGuess::from_strs(vec!["text/x-qml".to_string()]).unwrap();
+        assert_eq!(custom.first_raw(), Some("text/x-qml"));
+        assert_eq!(custom.to_static_guess(), None);
+
+        // same types as `gz`, but in a different order
+        let reordered = OwnedMimeGuess::from_strs(&["application/x-gzip", "application/gzip"]);
+        assert_eq!(reordered.unwrap().to_static_guess(), None);
+
+        let empty = OwnedMimeGuess::from_strs(Vec::<String>::new()).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.to_static_guess(), Some(from_ext("blahblah")));
+
+        assert!(OwnedMimeGuess::from_strs(&["image/png", "not-a-mime"]).is_err());
+
+        let deduped = OwnedMimeGuess::from_strs(&["image/png", "Image/PNG"]).unwrap();
+        assert_eq!(deduped.iter_raw().collect::<Vec<_>>(), ["image/png"]);
+    }
+
+    #[test]
+    fn test_merge() {
+        let by_content = MimeGuess::from_static(&["image/svg+xml"]);
+
+        let merged = from_ext("svg").merge(by_content);
+        assert_eq!(merged.iter_raw().collect::<Vec<_>>(), ["image/svg+xml"]);
+
+        let merged = from_ext("gz").merge(MimeGuess::from_static(&[
+            "application/X-GZIP",
+            "application/zip",
+            "application/gzip",
+        ]));
+        assert_eq!(
+            merged.iter_raw().collect::<Vec<_>>(),
+            ["application/gzip", "application/x-gzip", "application/zip"]
+        );
+
+        assert_eq!(
+            from_ext("blahblah").merge(from_ext("gif")),
+            from_ext("gif").into()
+        );
+        assert!(from_ext("blahblah").merge(from_ext("blahblah")).is_empty());
+    }
+
+    #[test]
+    fn test_from_path_bytes() {
+        let png = from_ext("png");
+        assert_eq!(MimeGuess::from_path_bytes(b"pic.png"), png);
+        assert_eq!(MimeGuess::from_path_bytes(b"/tmp/\xFF\x00\xE9pic.PNG"), png);
+        assert_eq!(MimeGuess::from_path_bytes(b"dir.gif/pic.png"), png);
+        assert_eq!(MimeGuess::from_path_bytes(b"a.b.tar.gz"), from_ext("gz"));
+
+        assert!(MimeGuess::from_path_bytes(b"pic.pn\xFF").is_empty());
+        assert!(MimeGuess::from_path_bytes(b"dir.png/").is_empty());
+        assert!(MimeGuess::from_path_bytes(b"dir.png/pic").is_empty());
+        assert!(MimeGuess::from_path_bytes(b"/tmp/.png").is_empty());
+        assert!(MimeGuess::from_path_bytes(b"..").is_empty());
+        assert!(MimeGuess::from_path_bytes(b"").is_empty());
+    }
+
+    #[test]
+    fn test_canonicalize_mime() {
+        use super::canonicalize;
+
+        let js = canonicalize(&"application/x-javascript".parse().unwrap());
+        assert_eq!(js, mime::TEXT_JAVASCRIPT);
+
+        let jpg = canonicalize(&"IMAGE/JPG; q=0.9".parse().unwrap());
+        assert_eq!(jpg.to_string(), "image/jpeg; q=0.9");
+
+        assert_eq!(canonicalize(&mime::IMAGE_PNG), mime::IMAGE_PNG);
+        assert_eq!(
+            canonicalize(&mime::TEXT_PLAIN_UTF_8),
+            mime::TEXT_PLAIN_UTF_8
+        );
+    }
+
+    #[test]
+    fn test_normalize_extension() {
+        use super::normalize_extension;
+
+        assert_eq!(normalize_extension("jpeg"), "jpg");
+        assert_eq!(normalize_extension("JPEG"), "jpg");
+        assert_eq!(normalize_extension("htm"), "html");
+        assert_eq!(normalize_extension("tif"), "tiff");
+        assert_eq!(normalize_extension("mpeg"), "mpg");
+
+        assert_eq!(normalize_extension("jpg"), "jpg");
+        assert_eq!(normalize_extension("PNG"), "PNG");
+        assert_eq!(normalize_extension("blahblah"), "blahblah");
+        assert_eq!(normalize_extension(""), "");
+    }
+
+    #[cfg(feature = "capi")]
+    #[test]
+    fn test_capi() {
+        use super::capi::mime_guess_from_ext;
+        use std::ffi::CStr;
+        use std::ptr;
+
+        unsafe {
+            let gif = mime_guess_from_ext(b"GIF\0".as_ptr() as *const _);
+            assert!(!gif.is_null());
+            assert_eq!(CStr::from_ptr(gif).to_str(), Ok("image/gif"));
+            // borrowed from a static table rather than allocated per call
+            assert_eq!(mime_guess_from_ext(b"gif\0".as_ptr() as *const _), gif);
+
+            for &(ext, _) in MIME_TYPES {
+                let c_ext = format!("{}\0", ext);
+                let mime = mime_guess_from_ext(c_ext.as_ptr() as *const _);
+                let expected = from_ext(ext).first_raw().unwrap();
+                assert_eq!(CStr::from_ptr(mime).to_str(), Ok(expected), "{:?}", ext);
+            }
+
+            assert!(mime_guess_from_ext(b"blahblah\0".as_ptr() as *const _).is_null());
+            assert!(mime_guess_from_ext(b"gi\xFF\0".as_ptr() as *const _).is_null());
+            assert!(mime_guess_from_ext(ptr::null()).is_null());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "path")]
+    fn test_from_paths() {
+        let paths = vec!["a/b.gif", "c.PNG", "d", "e.tar.gz"];
+        let guesses: Vec<_> = MimeGuess::from_paths(paths.clone()).collect();
+
+        assert_eq!(guesses.len(), paths.len());
+        for (&path, (guessed_path, guess)) in paths.iter().zip(guesses) {
+            assert_eq!(path, guessed_path);
+            assert_eq!(guess, from_path(path));
+        }
+
+        let owned = vec![Path::new("f.json").to_path_buf()];
+        let (path, guess) = MimeGuess::from_paths(owned).next().unwrap();
+        assert_eq!(path, Path::new("f.json"));
+        assert_eq!(guess, from_ext("json"));
+    }
+
+    #[test]
+    fn test_from_exts() {
+        let tracked = MimeGuess::from_exts(vec!["blahblah", "JS", "mjs"]);
+        assert_eq!(tracked.matched_extension(), Some("js"));
+        assert_eq!(tracked.guess(), from_ext("js"));
+
+        let tracked = MimeGuess::from_exts(vec!["mjs", "js"]);
+        assert_eq!(tracked.matched_extension(), Some("mjs"));
+
+        let tracked = MimeGuess::from_exts(vec!["", "blahblah"]);
+        assert_eq!(tracked.matched_extension(), None);
+        assert!(tracked.guess().is_empty());
+        assert!(MimeGuess::from_exts(Vec::new()).guess().is_empty());
+    }
+
+    #[test]
+    fn test_first_or_octet_stream_marked() {
+        assert_eq!(
+            from_ext("gif").first_or_octet_stream_marked(),
+            (mime::IMAGE_GIF, false)
+        );
+        assert_eq!(
+            from_ext("bin").first_or_octet_stream_marked(),
+            (mime::APPLICATION_OCTET_STREAM, false)
+        );
+        assert_eq!(
+            from_ext("blahblah").first_or_octet_stream_marked(),
+            (mime::APPLICATION_OCTET_STREAM, true)
+        );
+    }
+
+    #[test]
+    fn test_is_known_extension() {
+        use super::is_known_extension;
+
+        assert!(is_known_extension("gif"));
+        assert!(is_known_extension("GIF"));
+        assert!(is_known_extension("tar.gz"));
+        assert!(!is_known_extension("zzz"));
+        assert!(!is_known_extension(""));
+        assert!(!is_known_extension(".gif"));
+    }
+
+    #[test]
+    fn test_owned_collect_extend() {
+        let types = vec![
+            "text/html",
+            "image/png",
+            "TEXT/HTML",
+            "not-a-mime",
+            "image/png",
+        ];
+        let collected: OwnedMimeGuess = types.into_iter().collect();
+        assert_eq!(
+            collected.iter_raw().collect::<Vec<_>>(),
+            ["text/html", "image/png"]
+        );
+
+        let mut extended = collected.clone();
+        extended.extend(vec!["image/gif".to_string(), "image/PNG".to_string()]);
+        assert_eq!(
+            extended.iter_raw().collect::<Vec<_>>(),
+            ["text/html", "image/png", "image/gif"]
+        );
+
+        let invalid: OwnedMimeGuess = vec!["not-a-mime", "image png"].into_iter().collect();
+        assert!(invalid.is_empty());
+
+        let gz = from_ext("gz");
+        let collected: OwnedMimeGuess = gz.iter_raw().chain(from_ext("zip").iter_raw()).collect();
+        assert_eq!(collected, gz.merge(from_ext("zip")));
+    }
+
+    #[test]
+    fn test_builder() {
+        let default = MimeGuessBuilder::new();
+        assert_eq!(default.guess_ext("GIF"), from_ext("gif"));
+        assert!(default.guess_ext(".gif").is_empty());
+
+        let strict = default.case_sensitive(true).strip_leading_dot(true);
+        assert_eq!(strict.guess_ext("*.gif"), from_ext("gif"));
+        assert!(strict.guess_ext(".GIF").is_empty());
+
+        #[cfg(feature = "path")]
+        {
+            assert_eq!(default.guess_path("a.tar.gz"), from_ext("gz"));
+            assert!(strict.guess_path("a.GIF").is_empty());
+
+            let compound = strict.compound_extensions(true);
+            assert_eq!(compound.guess_path("a.tar.gz"), from_ext("tar.gz"));
+            assert_eq!(compound.guess_path("a.TAR.gz"), from_ext("gz"));
+            assert!(compound.guess_path("a.TAR.GZ").is_empty());
+            assert_eq!(
+                compound.case_sensitive(false).guess_path("a.TAR.GZ"),
+                from_ext("tar.gz")
+            );
+            assert!(compound.guess_path("tar.gz/.").is_empty());
+        }
+    }
+
+    #[test]
+    fn test_is_text() {
+        for ext in &["txt", "html", "json", "xml", "svg", "sh", "jsonld", "mjs"] {
+            assert!(from_ext(ext).is_text(), "{:?}", ext);
+        }
+
+        for ext in &["png", "zip", "pdf", "bin", "blahblah"] {
+            assert!(!from_ext(ext).is_text(), "{:?}", ext);
+        }
+
+        assert!(MimeGuess::from_static(&["application/XML"]).is_text());
+        assert!(!MimeGuess::from_static(&["application/xmlx"]).is_text());
+    }
+
+    #[test]
+    fn test_compressible_table() {
+        use super::compressible::COMPRESSIBLE;
+
+        for (&(mime, _), &(n_mime, _)) in COMPRESSIBLE.iter().zip(COMPRESSIBLE.iter().skip(1)) {
+            assert!(
+                mime < n_mime,
+                "COMPRESSIBLE not sorted: {:?} < {:?}",
+                mime,
+                n_mime
+            );
+        }
+
+        for &(mime, _) in COMPRESSIBLE {
+            mime.parse::<mime::Mime>().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_is_compressible() {
+        for ext in &[
+            "txt", "html", "css", "js", "json", "svg", "bmp", "ttf", "wasm",
+        ] {
+            assert!(from_ext(ext).is_compressible(), "{:?}", ext);
+        }
+
+        for ext in &[
+            "png", "jpg", "gif", "zip", "gz", "woff", "woff2", "mp4", "mp3", "wav", "epub", "odt",
+            "blahblah",
+        ] {
+            assert!(!from_ext(ext).is_compressible(), "{:?}", ext);
+        }
+    }
+
+    #[test]
+    fn test_is_ambiguous_ext() {
+        assert!(is_ambiguous_ext("ttf"));
+        assert!(is_ambiguous_ext("GZ"));
+        assert!(!is_ambiguous_ext("gif"));
+        assert!(!is_ambiguous_ext("blahblah"));
+        assert!(!is_ambiguous_ext(""));
+    }
+
+    #[test]
+    fn test_all_interpretations() {
+        let exts: Vec<&str> = all_interpretations("report.2024.final.docx")
+            .into_iter()
+            .map(|(ext, _)| ext)
+            .collect();
+        assert_eq!(exts, ["docx"]);
+
+        let interpretations = all_interpretations("notes.md.txt");
+        assert_eq!(interpretations[0], ("txt", from_ext("txt")));
+        assert_eq!(interpretations[1], ("md", from_ext("md")));
+        assert_eq!(interpretations.len(), 2);
+
+        assert!(all_interpretations(".bashrc").is_empty());
+        assert!(all_interpretations("no_extension").is_empty());
+    }
+
+    #[test]
+    fn test_are_mime_types_parseable() {
+        for (_, mimes) in MIME_TYPES {
+            mimes.iter().for_each(|s| {
+                expect_mime(s);
+            });
+        }
+    }
+
+    // RFC: Is this test necessary anymore? --@cybergeek94, 2/1/2016
+    #[test]
+    fn test_are_extensions_ascii() {
+        for (ext, _) in MIME_TYPES {
+            assert!(ext.is_ascii(), "Extension not ASCII: {:?}", ext);
+        }
+    }
+
+    #[test]
+    fn test_are_extensions_sorted() {
+        // simultaneously checks the requirement that duplicate extension entries are adjacent
+        for (&(ext, _), &(n_ext, _)) in MIME_TYPES.iter().zip(MIME_TYPES.iter().skip(1)) {
+            assert!(
+                ext <= n_ext,
+                "Extensions in src/mime_types should be sorted lexicographically
+                in ascending order. Failed assert: {:?} <= {:?}",
+                ext,
+                n_ext
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_get_mime_extensions_preferred_first() {
+        use super::preferred_exts::PREFERRED_EXTENSIONS;
+
+        assert_eq!(
+            super::get_mime_extensions(&mime::IMAGE_JPEG).unwrap(),
+            ["jpg", "jfif", "jpe", "jpeg"]
+        );
+
+        for &(mime, ext) in PREFERRED_EXTENSIONS {
+            let exts = super::get_mime_extensions(&mime.parse().unwrap()).unwrap();
+            assert_eq!(exts.first(), Some(&ext), "{:?}", mime);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_get_mime_extensions_message_multipart() {
+        let rfc822 = super::get_mime_extensions(&"message/rfc822".parse().unwrap()).unwrap();
+        assert!(rfc822.contains(&"eml"), "{:?}", rfc822);
+        assert!(rfc822.contains(&"mime"), "{:?}", rfc822);
+        assert_eq!(from_ext("eml").first_raw(), Some("message/rfc822"));
+
+        assert_eq!(get_mime_extensions_str("multipart/form-data"), None);
+        assert_eq!(get_mime_extensions_str("multipart/x-unknown"), None);
+        assert_eq!(get_mime_extensions_str("message/x-unknown"), None);
+    }
+
+    /// Every extension must be found again by reverse-looking-up each of its types, and every
+    /// reverse mapping must point to an extension whose forward lookup yields that type.
+    ///
+    /// There are currently no expected exceptions: both directions are generated from
+    /// `MIME_TYPES` without any alias normalization.
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_forward_reverse_round_trip() {
+        let mut violations = Vec::new();
+
+        for &(ext, mimes) in MIME_TYPES {
+            for mime_str in mimes {
+                let exts = get_mime_extensions_str(mime_str).unwrap_or(&[]);
+
+                if !exts.iter().any(|e| e.eq_ignore_ascii_case(ext)) {
+                    violations.push(format!("{:?} -> {:?} -> {:?}", ext, mime_str, exts));
+                }
+
+                for rev_ext in exts {
+                    if !from_ext(rev_ext)
+                        .iter_raw()
+                        .any(|m| m.eq_ignore_ascii_case(mime_str))
+                    {
+                        violations.push(format!(
+                            "{:?} -> {:?} -> {:?}",
+                            mime_str,
+                            rev_ext,
+                            from_ext(rev_ext)
+                        ));
+                    }
+                }
+            }
+        }
+
+        assert!(
+            violations.is_empty(),
+            "round-trip violations:\n{}",
+            violations.join("\n")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_get_mime_extensions_count() {
+        use super::{get_mime_extensions, get_mime_extensions_count};
+
+        let image_all = "image/*".parse().unwrap();
+        let image_count = get_mime_extensions_count(&image_all);
+        assert!(image_count > 1);
+        assert_eq!(image_count, get_mime_extensions(&image_all).unwrap().len());
+
+        assert_eq!(get_mime_extensions_count(&mime::IMAGE_GIF), 1);
+        assert_eq!(
+            get_mime_extensions_count(&"image/x-blahblah".parse().unwrap()),
+            0
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_mime_extensions() {
+        use super::{get_mime_extensions, mime_extensions};
+
+        assert_eq!(
+            mime_extensions(&mime::IMAGE_GIF).collect::<Vec<_>>(),
+            ["gif"]
+        );
+        assert_eq!(
+            mime_extensions(&"image/x-blahblah".parse().unwrap()).count(),
+            0
+        );
+
+        let image_all = "image/*".parse().unwrap();
+        let image_exts: Vec<_> = mime_extensions(&image_all).collect();
+        assert!(image_exts.contains(&"png") && image_exts.contains(&"gif"));
+        assert_eq!(image_exts, get_mime_extensions(&image_all).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_preferred_extension() {
+        use super::preferred_extension;
+
+        assert_eq!(preferred_extension(&mime::IMAGE_JPEG), Some("jpg"));
+        assert_eq!(preferred_extension(&mime::TEXT_PLAIN), Some("txt"));
+        assert_eq!(preferred_extension(&mime::TEXT_PLAIN_UTF_8), Some("txt"));
+        assert_eq!(preferred_extension(&mime::TEXT_HTML), Some("html"));
+        assert_eq!(preferred_extension(&mime::IMAGE_GIF), Some("gif"));
+        assert_eq!(
+            preferred_extension(&"image/x-blahblah".parse().unwrap()),
+            None
+        );
+        assert_eq!(preferred_extension(&mime::IMAGE_STAR), None);
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_preferred_extensions() {
+        use super::preferred_exts::PREFERRED_EXTENSIONS;
+
+        for (&(mime, _), &(n_mime, _)) in PREFERRED_EXTENSIONS
+            .iter()
+            .zip(PREFERRED_EXTENSIONS.iter().skip(1))
+        {
+            assert!(
+                mime < n_mime,
+                "PREFERRED_EXTENSIONS not sorted: {:?} < {:?}",
+                mime,
+                n_mime
+            );
+        }
+
+        for &(mime, ext) in PREFERRED_EXTENSIONS {
+            assert!(
+                from_ext(ext).iter_raw().any(|m| m == mime),
+                "{:?} not a type of {:?}",
+                mime,
+                ext
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_mimes_for_extension_glob() {
+        use super::mimes_for_extension_glob;
+
+        let ml: Vec<_> = mimes_for_extension_glob("*ml").collect();
+        for ext in &["html", "xml", "xhtml", "yaml"] {
+            assert!(ml.iter().any(|&(e, _)| e == *ext), "{:?} {:?}", ext, ml);
+        }
+        assert!(ml.iter().all(|&(e, _)| e.ends_with("ml")), "{:?}", ml);
+        assert!(ml.contains(&("html", "text/html")));
+
+        let gz: Vec<_> = mimes_for_extension_glob("G?")
+            .filter(|&(e, _)| e == "gz")
+            .collect();
+        assert_eq!(
+            gz,
+            [("gz", "application/gzip"), ("gz", "application/x-gzip")]
+        );
+
+        let tar: Vec<_> = mimes_for_extension_glob("tar.*").map(|(e, _)| e).collect();
+        assert!(tar.contains(&"tar.gz"), "{:?}", tar);
+
+        assert_eq!(
+            mimes_for_extension_glob("*").count(),
+            MIME_TYPES.iter().map(|&(_, m)| m.len()).sum::<usize>()
+        );
+        assert_eq!(mimes_for_extension_glob("").count(), 0);
+        assert_eq!(mimes_for_extension_glob("blah*blah").count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_glob_matches() {
+        use super::glob_matches;
+
+        assert!(glob_matches("*ml", "html"));
+        assert!(glob_matches("*ml", "ml"));
+        assert!(glob_matches("h*l", "HTML"));
+        assert!(glob_matches("*t*l", "xhtml"));
+        assert!(glob_matches("??", "gz"));
+        assert!(glob_matches("**", ""));
+        assert!(!glob_matches("??", "g"));
+        assert!(!glob_matches("*ml", "mlx"));
+        assert!(!glob_matches("", "gz"));
+
+        // a `*` in `pattern` is a wildcard even where `text` has a literal `*`
+        assert!(glob_matches("*", "*x"));
+        assert!(glob_matches("*x", "*x"));
+        assert!(glob_matches("a*", "a*bc"));
+        assert!(glob_matches("*b*", "a*b*c"));
+        assert!(!glob_matches("*y", "*x"));
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_extensions_for_suffix() {
+        use super::extensions_for_suffix;
+
+        let xml: Vec<_> = extensions_for_suffix("xml").collect();
+        assert!(xml.contains(&"svg") && xml.contains(&"xhtml"), "{:?}", xml);
+        assert!(!xml.contains(&"xml"), "{:?}", xml);
+
+        let json: Vec<_> = extensions_for_suffix("JSON").collect();
+        assert!(
+            json.contains(&"jsonld") && json.contains(&"geojson"),
+            "{:?}",
+            json
+        );
+        assert!(!json.contains(&"json"), "{:?}", json);
+
+        assert_eq!(extensions_for_suffix("").count(), 0);
+        assert_eq!(extensions_for_suffix("blahblah").count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_suggested_filename() {
+        let pdf = from_ext("pdf");
+        assert_eq!(pdf.suggested_filename("report"), Some("report.pdf".into()));
+        assert_eq!(
+            pdf.suggested_filename("report.PDF"),
+            Some("report.PDF".into())
+        );
+        assert_eq!(
+            pdf.suggested_filename("report.txt"),
+            Some("report.txt.pdf".into())
+        );
+
+        let jpeg = from_ext("jpeg");
+        assert_eq!(jpeg.suggested_filename("photo"), Some("photo.jpg".into()));
+        assert_eq!(
+            jpeg.suggested_filename("photo.jpe"),
+            Some("photo.jpe".into())
+        );
+
+        let no_ext = MimeGuess::from_static(&["application/x-blahblah"]);
+        assert_eq!(no_ext.suggested_filename("file"), None);
+        assert_eq!(from_ext("blahblah").suggested_filename("file"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_get_mime_extensions_lenient() {
+        use super::{get_mime_extensions, get_mime_extensions_lenient};
+
+        for mime_str in &[
+            "text/html; charset=utf-8",
+            "TEXT/HTML;charset=utf-8",
+            "image/*; q=0.5",
+            "*/*;q=0.1",
+            "image/gif",
+        ] {
+            let mime: mime::Mime = mime_str.parse().unwrap();
+            assert_eq!(
+                get_mime_extensions_lenient(mime_str),
+                get_mime_extensions(&mime),
+                "{:?}",
+                mime_str
+            );
+            assert_eq!(
+                get_mime_extensions_str(mime_str),
+                get_mime_extensions(&mime),
+                "{:?}",
+                mime_str
+            );
+        }
+
+        let html = get_mime_extensions(&mime::TEXT_HTML);
+        assert_eq!(get_mime_extensions_lenient("  text / html ; q=0.5 "), html);
+
+        let image = get_mime_extensions(&mime::IMAGE_STAR);
+        assert_eq!(get_mime_extensions_lenient("image"), image);
+        assert_eq!(get_mime_extensions_lenient("image/"), image);
+        assert_eq!(get_mime_extensions_lenient("image/;q=0.5"), image);
+
+        assert_eq!(get_mime_extensions_lenient(""), None);
+        assert_eq!(get_mime_extensions_lenient("/html"), None);
+        assert_eq!(get_mime_extensions_lenient("image/x-blahblah"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_extensions_for_accept() {
+        use super::{extensions_for_accept, get_mime_extensions};
+
+        let accept = "text/html,application/xhtml+xml,application/xml;q=0.9,\
+                      image/avif,image/webp,*/*;q=0.8";
+        let exts = extensions_for_accept(accept);
+        let pos = |ext| exts.iter().position(|&e| e == ext).unwrap();
+
+        let html = get_mime_extensions(&mime::TEXT_HTML).unwrap();
+        assert_eq!(&exts[..html.len()], html);
+        assert!(pos("xhtml") < pos("avif") && pos("avif") < pos("webp"));
+        assert!(pos("webp") < pos("asax"), "application/xml has q=0.9");
+        assert!(pos("asax") < pos("png"), "*/* has q=0.8");
+        assert_eq!(exts.len(), all_extensions().count());
+
+        let exts = extensions_for_accept("image/*;q=0.5, image/png;q=bogus, image/gif;q=0");
+        assert_eq!(exts[0], "png");
+        assert!(exts.contains(&"jpg"));
+        assert!(!exts.contains(&"gif"));
+
+        assert!(extensions_for_accept("").is_empty());
+        assert!(extensions_for_accept("garbage, /, text/x-blahblah").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "rev-mappings")]
+    fn test_extension_matches() {
+        use super::extension_matches;
+
+        assert!(extension_matches(&mime::IMAGE_JPEG, "jpg"));
+        assert!(extension_matches(&mime::IMAGE_JPEG, "JPEG"));
+        assert!(!extension_matches(&mime::IMAGE_JPEG, "png"));
+        assert!(!extension_matches(&mime::IMAGE_JPEG, ""));
+
+        assert!(extension_matches(&mime::IMAGE_STAR, "png"));
+        assert!(!extension_matches(
+            &"image/x-blahblah".parse().unwrap(),
+            "png"
+        ));
     }
 
     #[test]
     fn test_get_mime_extensions_str_no_panic_if_bad_mime() {
         assert_eq!(get_mime_extensions_str(""), None);
+        assert_eq!(get_mime_extensions_str("notamime"), None);
+        assert_eq!(get_mime_extensions_str("/"), None);
+        assert_eq!(get_mime_extensions_str("*/"), None);
+        assert_eq!(get_mime_extensions_str("/*"), None);
+        assert_eq!(get_mime_extensions_str("image/"), None);
+        assert_eq!(get_mime_extensions_str("; charset=utf-8"), None);
     }
 }
@@ -0,0 +1,51 @@
+use unicase::UniCase;
+
+/// A mapping of obsolete or unregistered media types to the current types which supersede them.
+///
+/// Required to be sorted lexicographically by the obsolete type. Please cite the document
+/// deprecating the old type in the commit message when adding entries, as for `MIME_TYPES`.
+///
+/// Sourced from:
+/// * JavaScript: [RFC 9239, Section 6](https://www.rfc-editor.org/rfc/rfc9239#section-6)
+/// * Fonts: [RFC 8081, Section 4](https://tools.ietf.org/html/rfc8081#section-4)
+/// * gzip: [RFC 6713, Section 3](https://tools.ietf.org/html/rfc6713#section-3)
+/// * Markdown: [RFC 7763, Section 2](https://tools.ietf.org/html/rfc7763#section-2)
+/// * XML: [RFC 7303, Section 9.2](https://tools.ietf.org/html/rfc7303#section-9.2)
+/// * YAML: [RFC 9512, Section 2.1](https://www.rfc-editor.org/rfc/rfc9512#section-2.1)
+/// * `image/jpg` is a common misspelling of `image/jpeg`, which was never registered.
+pub static MIME_ALIASES: &[(&str, &str)] = &[
+    ("application/ecmascript", "text/javascript"),
+    ("application/font-sfnt", "font/sfnt"),
+    ("application/font-woff", "font/woff"),
+    ("application/javascript", "text/javascript"),
+    ("application/x-ecmascript", "text/javascript"),
+    ("application/x-font-otf", "font/otf"),
+    ("application/x-font-ttf", "font/ttf"),
+    ("application/x-gzip", "application/gzip"),
+    ("application/x-javascript", "text/javascript"),
+    ("application/x-yaml", "application/yaml"),
+    ("image/jpg", "image/jpeg"),
+    ("text/ecmascript", "text/javascript"),
+    ("text/javascript1.0", "text/javascript"),
+    ("text/javascript1.1", "text/javascript"),
+    ("text/javascript1.2", "text/javascript"),
+    ("text/javascript1.3", "text/javascript"),
+    ("text/javascript1.4", "text/javascript"),
+    ("text/javascript1.5", "text/javascript"),
+    ("text/jscript", "text/javascript"),
+    ("text/livescript", "text/javascript"),
+    ("text/x-ecmascript", "text/javascript"),
+    ("text/x-javascript", "text/javascript"),
+    ("text/x-markdown", "text/markdown"),
+    ("text/x-yaml", "application/yaml"),
+    ("text/xml", "application/xml"),
+];
+
+/// Get the current media type superseding the `<type>/<subtype>` essence `obsolete`, or `None`
+/// if it is not known to be obsolete. Case-insensitive.
+pub fn get_current(obsolete: &str) -> Option<&'static str> {
+    MIME_ALIASES
+        .binary_search_by_key(&UniCase::new(obsolete), |&(old, _)| UniCase::new(old))
+        .ok()
+        .map(|i| MIME_ALIASES[i].1)
+}
@@ -5,6 +5,10 @@
 /// Multiple MIME types per extension are supported; the order is arbitrary but the first should be
 /// the most prevalent by most recent RFC declaration or explicit succession of other media types.
 ///
+/// Compound extensions spanning several dot-separated segments, such as `tar.gz`, are listed like
+/// any other extension; `Path::extension()` only ever returns the last segment, so these are
+/// only matched by `MimeGuess::from_path_compound()` or when passed to `from_ext()` directly.
+///
 /// NOTE: when adding or modifying entries, please include a citation in the commit message.
 /// If a media type for an extension changed by official IETF RFC, please keep the old entry but add
 /// the new one before it in the slice literal, e.g.:
@@ -185,6 +189,7 @@ pub static MIME_TYPES: &[(&str, &[&str])] = &[
         &["application/vnd.anser-web-certificate-issue-initiation"],
     ),
     ("cil", &["application/vnd.ms-artgalry"]),
+    ("cjs", &["text/javascript", "application/node"]),
     ("cla", &["application/vnd.claymore"]),
     ("class", &["application/x-java-applet"]),
     ("clkk", &["application/vnd.crick.clicker.keyboard"]),
@@ -666,7 +671,7 @@ pub static MIME_TYPES: &[(&str, &[&str])] = &[
     ("maker", &["application/vnd.framemaker"]),
     ("man", &["application/x-troff-man"]),
     ("manifest", &["application/x-ms-manifest"]),
-    ("map", &["text/plain"]),
+    ("map", &["application/json", "text/plain"]),
     ("mar", &["application/octet-stream"]),
     ("markdown", &["text/markdown"]),
     ("master", &["application/xml"]),
@@ -703,7 +708,7 @@ pub static MIME_TYPES: &[(&str, &[&str])] = &[
     ("mix", &["application/octet-stream"]),
     ("mj2", &["video/mj2"]),
     ("mjp2", &["video/mj2"]),
-    ("mjs", &["application/javascript"]),
+    ("mjs", &["text/javascript", "application/javascript"]),
     ("mk", &["text/plain"]),
     ("mk3d", &["video/x-matroska"]),
     ("mka", &["audio/x-matroska"]),
@@ -853,7 +858,7 @@ pub static MIME_TYPES: &[(&str, &[&str])] = &[
         "otc",
         &["application/vnd.oasis.opendocument.chart-template"],
     ),
-    ("otf", &["application/font-sfnt"]),
+    ("otf", &["font/otf", "application/font-sfnt"]),
     (
         "otg",
         &["application/vnd.oasis.opendocument.graphics-template"],
@@ -1221,6 +1226,10 @@ pub static MIME_TYPES: &[(&str, &[&str])] = &[
     ("taglet", &["application/vnd.mynfc"]),
     ("tao", &["application/vnd.tao.intent-module-archive"]),
     ("tar", &["application/x-tar"]),
+    ("tar.bz2", &["application/x-bzip2-compressed-tar"]),
+    ("tar.gz", &["application/x-compressed-tar"]),
+    ("tar.xz", &["application/x-xz-compressed-tar"]),
+    ("tar.zst", &["application/x-zstd-compressed-tar"]),
     ("tcap", &["application/vnd.3gpp2.tcap"]),
     ("tcl", &["application/x-tcl"]),
     ("teacher", &["application/vnd.smart.teacher"]),
@@ -1408,7 +1417,7 @@ pub static MIME_TYPES: &[(&str, &[&str])] = &[
     ("wmv", &["video/x-ms-wmv"]),
     ("wmx", &["video/x-ms-wmx"]),
     ("wmz", &["application/x-ms-wmz"]),
-    ("woff", &["application/font-woff"]),
+    ("woff", &["font/woff", "application/font-woff"]),
     ("woff2", &["font/woff2"]),
     ("wpd", &["application/vnd.wordperfect"]),
     ("wpl", &["application/vnd.ms-wpl"]),
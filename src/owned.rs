@@ -0,0 +1,162 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+use mime::FromStrError;
+
+use {expect_mime, fmt_media_types, impl_, Mime, MimeGuess};
+
+/// An owned "guess" of the MIME/Media Type(s) of an extension or path.
+///
+/// Unlike [`MimeGuess`](struct.MimeGuess.html), which borrows the crate's static table,
+/// this can hold arbitrary media-type strings, such as the result of
+/// [`MimeGuess::canonicalize()`](struct.MimeGuess.html#method.canonicalize).
+///
+/// See [Note: Ordering on `MimeGuess`](struct.MimeGuess.html#note-ordering).
+///
+/// ### Note: Serialization
+/// With the `serde` feature, this implements `Serialize` and `Deserialize` as a sequence of its
+/// media-type strings, so it can round-trip the serialized form of a `MimeGuess`.
+/// Deserialization fails if any of the strings is not a valid `Mime`.
+///
+/// ### Note: Parsing and Display
+/// `Display` writes the media types separated by `, `, the same as for `MimeGuess`, and
+/// `FromStr` parses that form back: a comma-separated list of media types, each of which
+/// must be a valid `Mime`. Surrounding whitespace is trimmed and an empty (or all-whitespace)
+/// string gives an empty guess. The strings are kept as written rather than interned into
+/// the crate's table, so any media type can be parsed.
+///
+/// ### Note: Duplicates
+/// A guess never contains the same media type twice (compared case-insensitively). However it
+/// is built, duplicates are skipped and the first occurrence is kept, as for
+/// [`MimeGuess::merge()`](struct.MimeGuess.html#method.merge).
+///
+/// ### Note: Collecting
+/// A guess can also be collected from, or extended with, an iterator of media-type strings.
+/// Unlike the other ways of building a guess, this cannot fail: strings which are not valid
+/// `Mime`s are skipped. Use [`from_strs()`](#method.from_strs) to reject them instead.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OwnedMimeGuess(pub(crate) Vec<String>);
+
+impl OwnedMimeGuess {
+    /// Create a guess from a list of media-type strings, in order.
+    ///
+    /// Returns an error if any of the strings is not a valid `Mime`. The strings are kept as
+    /// given, not normalized, but duplicates are skipped.
+    pub fn from_strs<I, S>(mimes: I) -> Result<Self, FromStrError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut guess = OwnedMimeGuess::default();
+
+        for mime in mimes {
+            let mime = mime.as_ref();
+            mime.parse::<Mime>()?;
+            guess.push_unique(mime);
+        }
+
+        Ok(guess)
+    }
+
+    /// Append `mime` unless the guess already contains it, compared case-insensitively.
+    ///
+    /// Does not check that `mime` is valid.
+    pub(crate) fn push_unique(&mut self, mime: &str) {
+        if !self.0.iter().any(|m| m.eq_ignore_ascii_case(mime)) {
+            self.0.push(mime.to_owned());
+        }
+    }
+
+    /// Get the equivalent `MimeGuess` borrowing the crate's static table, if there is one.
+    ///
+    /// This is the case if the guess is empty, or if some extension in the table maps to
+    /// exactly the same media types (compared case-sensitively) in the same order, as for any
+    /// guess converted from a `MimeGuess`. Returns `None` otherwise.
+    pub fn to_static_guess(&self) -> Option<MimeGuess> {
+        if self.is_empty() {
+            return Some(MimeGuess::from_static(&[]));
+        }
+
+        impl_::entries()
+            .map(|(_, mimes)| mimes)
+            .find(|mimes| mimes.iter().cloned().eq(self.iter_raw()))
+            .map(MimeGuess::from_static)
+    }
+
+    /// `true` if the guess does not contain any media types.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the number of MIME types in the current guess.
+    pub fn count(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Get the first guessed `Mime`, if applicable.
+    pub fn first(&self) -> Option<Mime> {
+        self.first_raw().map(expect_mime)
+    }
+
+    /// Get the first guessed Media Type as a string, if applicable.
+    pub fn first_raw(&self) -> Option<&str> {
+        self.0.first().map(String::as_str)
+    }
+
+    /// Get an iterator over the `Mime` values contained in this guess.
+    pub fn iter(&self) -> impl Iterator<Item = Mime> + '_ {
+        self.iter_raw().map(expect_mime)
+    }
+
+    /// Get an iterator over the raw media-type strings in this guess.
+    pub fn iter_raw(&self) -> impl Iterator<Item = &str> + '_ {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl From<MimeGuess> for OwnedMimeGuess {
+    fn from(guess: MimeGuess) -> Self {
+        OwnedMimeGuess(guess.iter_raw().map(str::to_owned).collect())
+    }
+}
+
+impl fmt::Display for OwnedMimeGuess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_media_types(self.iter_raw(), f)
+    }
+}
+
+impl FromStr for OwnedMimeGuess {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(OwnedMimeGuess::default());
+        }
+
+        OwnedMimeGuess::from_strs(s.split(',').map(str::trim))
+    }
+}
+
+/// Strings which are not valid `Mime`s, and media types already in the guess, are skipped.
+impl<S: AsRef<str>> Extend<S> for OwnedMimeGuess {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for mime in iter {
+            let mime = mime.as_ref();
+
+            if mime.parse::<Mime>().is_ok() {
+                self.push_unique(mime);
+            }
+        }
+    }
+}
+
+/// Strings which are not valid `Mime`s, and duplicate media types, are skipped.
+impl<S: AsRef<str>> FromIterator<S> for OwnedMimeGuess {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut guess = OwnedMimeGuess::default();
+        guess.extend(iter);
+        guess
+    }
+}
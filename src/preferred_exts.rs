@@ -0,0 +1,30 @@
+use unicase::UniCase;
+
+/// A mapping of media types to the extension which should be preferred for them, e.g. when
+/// naming a file, where that is not simply the first extension for the type in `MIME_TYPES`
+/// which is not an alias (see `ext_aliases`).
+///
+/// Required to be sorted lexicographically by media type. Each extension must be in
+/// `MIME_TYPES` with the media type among its types.
+///
+/// Also included by `build.rs`, which lists each of these extensions first among the
+/// extensions for its type in the reverse mappings.
+pub static PREFERRED_EXTENSIONS: &[(&str, &str)] = &[
+    ("application/octet-stream", "bin"),
+    ("audio/mpeg", "mp3"),
+    ("image/jpeg", "jpg"),
+    ("text/javascript", "js"),
+    ("text/markdown", "md"),
+    ("text/plain", "txt"),
+    ("text/xml", "xml"),
+    ("video/mpeg", "mpg"),
+];
+
+/// Get the preferred extension for the `<type>/<subtype>` essence `mime`, if it has one listed.
+/// Case-insensitive.
+pub fn get_preferred(mime: &str) -> Option<&'static str> {
+    PREFERRED_EXTENSIONS
+        .binary_search_by_key(&UniCase::new(mime), |&(mime, _)| UniCase::new(mime))
+        .ok()
+        .map(|i| PREFERRED_EXTENSIONS[i].1)
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read};
+use std::{slice, str};
+
+use {expect_mime, Mime, MimeGuess, OwnedMimeGuess, MAX_EXTENSION_LEN};
+
+/// A runtime-extensible mapping of file extensions to MIME/Media Types.
+///
+/// The registry starts out with the crate's built-in table; mappings added with
+/// [`insert()`](#method.insert) or [`extend()`](#method.extend) take precedence over it.
+/// As with [`MimeGuess::from_ext()`](struct.MimeGuess.html#method.from_ext), lookups are
+/// case-insensitive, and they do not allocate for ASCII extensions of up to
+/// [`MAX_EXTENSION_LEN`](constant.MAX_EXTENSION_LEN.html) bytes.
+///
+/// ```rust
+/// use mime_guess::MimeRegistry;
+///
+/// let mut registry = MimeRegistry::new();
+/// registry.insert("qml", "text/x-qml".parse().unwrap());
+///
+/// assert_eq!(registry.guess("QML").first_raw(), Some("text/x-qml"));
+/// // extensions not registered at runtime fall through to the built-in table
+/// assert_eq!(registry.guess("gif").first_raw(), Some("image/gif"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MimeRegistry {
+    // keys are lowercased by `str::to_lowercase()`
+    user: HashMap<String, Vec<String>>,
+    builtin: bool,
+}
+
+impl MimeRegistry {
+    /// Create a registry containing only the built-in mappings.
+    pub fn new() -> Self {
+        MimeRegistry {
+            user: HashMap::new(),
+            builtin: true,
+        }
+    }
+
+    /// Create a registry with no mappings at all, not even the built-in ones.
+    pub fn empty() -> Self {
+        MimeRegistry {
+            user: HashMap::new(),
+            builtin: false,
+        }
+    }
+
+    /// Create a registry from the built-in mappings plus those in a `mime.types` file, in the
+    /// format used by Apache and others.
+    ///
+    /// See [`load_mime_types()`](#method.load_mime_types) for details; to use only the
+    /// mappings in the file, call that on [`MimeRegistry::empty()`](#method.empty) instead.
+    pub fn from_mime_types_file<R: Read>(r: R) -> io::Result<Self> {
+        let mut registry = Self::new();
+        registry.load_mime_types(r)?;
+        Ok(registry)
+    }
+
+    /// Add the mappings from a `mime.types` file, in the format used by Apache and others.
+    ///
+    /// Each line names a media type followed by zero or more extensions, separated by
+    /// whitespace, e.g. `text/html html htm`. Everything after a `#` is a comment; blank lines,
+    /// and lines naming a media type but no extensions, are ignored. An extension listed on
+    /// several lines is mapped to each of their media types, in the order they appear.
+    ///
+    /// Returns an error of kind `InvalidData` if a line does not start with a valid media type;
+    /// mappings from the lines before it are kept.
+    pub fn load_mime_types<R: Read>(&mut self, r: R) -> io::Result<()> {
+        for (i, line) in BufReader::new(r).lines().enumerate() {
+            let line = line?;
+            let line = match line.find('#') {
+                Some(comment) => &line[..comment],
+                None => &line[..],
+            };
+
+            let mut words = line.split_whitespace();
+
+            let mime = match words.next() {
+                Some(mime) => mime.parse::<Mime>().map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: invalid media type {:?}: {}", i + 1, mime, e),
+                    )
+                })?,
+                None => continue,
+            };
+
+            for ext in words {
+                self.insert(ext, mime.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Map `ext` to `mime`.
+    ///
+    /// Once an extension has been mapped at runtime, guesses for it consist only of the
+    /// runtime mappings, in the order they were inserted; the built-in mappings for it are
+    /// ignored. Inserting the same mapping twice has no effect.
+    pub fn insert(&mut self, ext: &str, mime: Mime) {
+        let mimes = self.user.entry(ext.to_lowercase()).or_default();
+
+        if !mimes.iter().any(|m| m == mime.as_ref()) {
+            mimes.push(mime.as_ref().to_owned());
+        }
+    }
+
+    /// Guess the MIME type of a file with the given extension.
+    ///
+    /// Runtime mappings for `ext` are consulted first; if there are none, this falls back to
+    /// [`MimeGuess::from_ext()`](struct.MimeGuess.html#method.from_ext) (unless the registry
+    /// was created [`empty()`](#method.empty)).
+    ///
+    /// The returned guess borrows from the registry or the built-in table rather than copying
+    /// the media types; see [`RegistryGuess`](enum.RegistryGuess.html).
+    pub fn guess(&self, ext: &str) -> RegistryGuess<'_> {
+        match self.get_user(ext) {
+            Some(mimes) => RegistryGuess::Runtime(mimes),
+            None if self.builtin => RegistryGuess::Builtin(MimeGuess::from_ext(ext)),
+            None => RegistryGuess::Builtin(MimeGuess::from_static(&[])),
+        }
+    }
+
+    fn get_user(&self, ext: &str) -> Option<&[String]> {
+        if self.user.is_empty() {
+            return None;
+        }
+
+        let mimes = if ext.len() <= MAX_EXTENSION_LEN && ext.is_ascii() {
+            // lowercase on the stack; for ASCII this is the same as `str::to_lowercase()`
+            let mut buf = [0u8; MAX_EXTENSION_LEN];
+            let key = &mut buf[..ext.len()];
+            key.copy_from_slice(ext.as_bytes());
+            key.make_ascii_lowercase();
+
+            self.user
+                .get(str::from_utf8(key).expect("ASCII is valid UTF-8"))
+        } else {
+            self.user.get(&ext.to_lowercase())
+        };
+
+        mimes.map(Vec::as_slice)
+    }
+}
+
+/// A guess returned by [`MimeRegistry::guess()`](struct.MimeRegistry.html#method.guess),
+/// borrowing the media types it contains.
+///
+/// Guesses compare equal if they contain the same media types in the same order, whichever
+/// variant they are. Use [`to_owned_guess()`](#method.to_owned_guess) to keep a guess beyond
+/// the lifetime of the registry.
+#[derive(Copy, Clone, Debug)]
+pub enum RegistryGuess<'a> {
+    /// A guess from the crate's built-in table, also used when there is no mapping at all.
+    Builtin(MimeGuess),
+    /// The mappings for an extension added to the registry at runtime.
+    Runtime(&'a [String]),
+}
+
+impl<'a> RegistryGuess<'a> {
+    /// `true` if the guess does not contain any media types.
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// Get the number of MIME types in the current guess.
+    pub fn count(&self) -> usize {
+        match *self {
+            RegistryGuess::Builtin(guess) => guess.count(),
+            RegistryGuess::Runtime(mimes) => mimes.len(),
+        }
+    }
+
+    /// Get the first guessed `Mime`, if applicable.
+    pub fn first(&self) -> Option<Mime> {
+        self.first_raw().map(expect_mime)
+    }
+
+    /// Get the first guessed Media Type as a string, if applicable.
+    pub fn first_raw(&self) -> Option<&'a str> {
+        self.iter_raw().next()
+    }
+
+    /// Get an iterator over the `Mime` values contained in this guess.
+    pub fn iter(&self) -> impl Iterator<Item = Mime> + 'a {
+        self.iter_raw().map(expect_mime)
+    }
+
+    /// Get an iterator over the raw media-type strings in this guess.
+    pub fn iter_raw(&self) -> RegistryIterRaw<'a> {
+        let (builtin, runtime): (&'a [&'a str], &'a [String]) = match *self {
+            RegistryGuess::Builtin(guess) => (guess.as_raw_slice(), &[]),
+            RegistryGuess::Runtime(mimes) => (&[], mimes),
+        };
+
+        RegistryIterRaw {
+            builtin: builtin.iter(),
+            runtime: runtime.iter(),
+        }
+    }
+
+    /// Copy the media types in this guess into an `OwnedMimeGuess`.
+    pub fn to_owned_guess(&self) -> OwnedMimeGuess {
+        self.iter_raw().collect()
+    }
+}
+
+impl<'a> From<MimeGuess> for RegistryGuess<'a> {
+    fn from(guess: MimeGuess) -> Self {
+        RegistryGuess::Builtin(guess)
+    }
+}
+
+impl<'a, 'b> PartialEq<RegistryGuess<'b>> for RegistryGuess<'a> {
+    fn eq(&self, other: &RegistryGuess<'b>) -> bool {
+        self.iter_raw().eq(other.iter_raw())
+    }
+}
+
+impl<'a> Eq for RegistryGuess<'a> {}
+
+/// An iterator over the raw media-type strings of a `RegistryGuess`.
+#[derive(Clone, Debug)]
+pub struct RegistryIterRaw<'a> {
+    // only one of these is ever non-empty
+    builtin: slice::Iter<'a, &'a str>,
+    runtime: slice::Iter<'a, String>,
+}
+
+impl<'a> Iterator for RegistryIterRaw<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        match self.builtin.next() {
+            Some(&mime) => Some(mime),
+            None => self.runtime.next().map(String::as_str),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.builtin.len() + self.runtime.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for RegistryIterRaw<'a> {}
+
+impl Default for MimeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Extend<(&'a str, Mime)> for MimeRegistry {
+    fn extend<I: IntoIterator<Item = (&'a str, Mime)>>(&mut self, iter: I) {
+        for (ext, mime) in iter {
+            self.insert(ext, mime);
+        }
+    }
+}
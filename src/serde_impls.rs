@@ -0,0 +1,36 @@
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+use {Mime, MimeGuess, OwnedMimeGuess};
+
+/// Serialized as a sequence of its media-type strings.
+impl Serialize for MimeGuess {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter_raw())
+    }
+}
+
+/// Serialized as a sequence of its media-type strings.
+impl Serialize for OwnedMimeGuess {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter_raw())
+    }
+}
+
+/// Deserialized from a sequence of media-type strings, each of which must be a valid `Mime`.
+/// Duplicates are skipped.
+impl<'de> Deserialize<'de> for OwnedMimeGuess {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mimes = Vec::<String>::deserialize(deserializer)?;
+        let mut guess = OwnedMimeGuess::default();
+
+        for mime_str in &mimes {
+            mime_str.parse::<Mime>().map_err(|e| {
+                D::Error::custom(format_args!("invalid media type {:?}: {}", mime_str, e))
+            })?;
+            guess.push_unique(mime_str);
+        }
+
+        Ok(guess)
+    }
+}
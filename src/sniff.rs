@@ -0,0 +1,186 @@
+use std::io::{self, Read, Seek, SeekFrom};
+#[cfg(feature = "path")]
+use std::path::Path;
+
+use MimeGuess;
+#[cfg(feature = "path")]
+use OwnedMimeGuess;
+
+/// The maximum number of bytes read from the start of the content to guess its type.
+pub const SNIFF_LEN: usize = 512;
+
+/// Known signatures ("magic numbers") at the start of file contents, and their MIME types.
+static SIGNATURES: &[(&[u8], &[&str])] = &[
+    (b"\x89PNG\r\n\x1a\n", &["image/png"]),
+    (b"\xFF\xD8\xFF", &["image/jpeg"]),
+    (b"GIF87a", &["image/gif"]),
+    (b"GIF89a", &["image/gif"]),
+    (b"%PDF-", &["application/pdf"]),
+    (b"PK\x03\x04", &["application/zip"]),
+    (b"\x1F\x8B", &["application/gzip"]),
+];
+
+const ZIP_SIGNATURE: &[u8] = b"PK\x03\x04";
+
+/// Formats based on ZIP which are identified by the name of an entry in the archive.
+static ZIP_ENTRY_PREFIXES: &[(&str, &str)] =
+    &[("word/", "docx"), ("xl/", "xlsx"), ("ppt/", "pptx")];
+
+impl MimeGuess {
+    /// Guess the MIME type of a file by both its path and its content, read from `reader`.
+    ///
+    /// The path is guessed from as with [`from_path()`](#method.from_path) and the content
+    /// as with [`sniff_reader_unseekable()`](#method.sniff_reader_unseekable), consuming up to
+    /// [`SNIFF_LEN`](sniff/constant.SNIFF_LEN.html) bytes from `reader`.
+    ///
+    /// ### Ordering
+    /// * If the guess from the path is empty, or just `application/octet-stream`, the guess from
+    ///   the content is returned (or, if that is empty too, the guess from the path).
+    /// * If the first type guessed from the content is among the types guessed from the path,
+    ///   they agree and the guess from the path is returned as-is.
+    /// * Otherwise they disagree and the content is trusted over the path: the types guessed
+    ///   from the content are returned first, followed by those guessed from the path (see
+    ///   [`merge()`](#method.merge)).
+    ///
+    /// Only available with the `sniff` feature.
+    #[cfg(feature = "path")]
+    pub fn from_path_and_content<P: AsRef<Path>, R: Read>(
+        path: P,
+        reader: &mut R,
+    ) -> io::Result<OwnedMimeGuess> {
+        let by_path = Self::from_path(path);
+        let (by_content, _) = Self::sniff_reader_unseekable(reader)?;
+
+        let is_generic = by_path.is_empty()
+            || by_path
+                .iter_raw()
+                .all(|m| m.eq_ignore_ascii_case("application/octet-stream"));
+
+        let guess = match by_content.first_raw() {
+            Some(_) if is_generic => OwnedMimeGuess::from(by_content),
+            Some(first) if !by_path.contains_str(first) => by_content.merge(by_path),
+            _ => OwnedMimeGuess::from(by_path),
+        };
+
+        Ok(guess)
+    }
+
+    /// Guess the MIME type of `bytes` by its signature (or "magic number").
+    ///
+    /// The following formats are currently recognized: PNG, JPEG, GIF, PDF, gzip and ZIP.
+    /// If several formats match, the most specific one is returned; currently this means
+    /// formats based on ZIP are recognized as such if possible instead of as plain ZIP archives:
+    ///
+    /// * Office Open XML documents (`docx`, `xlsx` and `pptx`), by the names of the entries of
+    ///   the archive contained in `bytes`;
+    /// * OpenDocument and EPUB files, by the media type stored in their leading `mimetype`
+    ///   entry, if known to the crate.
+    ///
+    /// If `bytes` does not match any format, including if it is shorter than the signature of
+    /// every format, an empty guess is returned.
+    ///
+    /// Only available with the `sniff` feature.
+    ///
+    /// ## Note
+    /// Contents are only checked for the signature of a format, so a match does not mean the
+    /// rest of the content is valid. Be as wary of the result as with guesses from extensions.
+    pub fn sniff_bytes(bytes: &[u8]) -> MimeGuess {
+        if bytes.starts_with(ZIP_SIGNATURE) {
+            if let Some(guess) = sniff_zip(bytes) {
+                return guess;
+            }
+        }
+
+        SIGNATURES
+            .iter()
+            .find(|&&(signature, _)| bytes.starts_with(signature))
+            .map_or(MimeGuess(&[]), |&(_, mimes)| MimeGuess(mimes))
+    }
+
+    /// Guess the MIME type of the content read from `reader` by its signature (or "magic
+    /// number"), restoring the position of `reader` afterwards.
+    ///
+    /// Reads up to [`SNIFF_LEN`](sniff/constant.SNIFF_LEN.html) bytes, which are then matched as
+    /// with [`sniff_bytes()`](#method.sniff_bytes).
+    ///
+    /// Only available with the `sniff` feature.
+    ///
+    /// ## Note
+    /// Contents are only checked for the signature of a format, so a match does not mean the
+    /// rest of the content is valid. Be as wary of the result as with guesses from extensions.
+    pub fn sniff_reader<R: Read + Seek>(reader: &mut R) -> io::Result<MimeGuess> {
+        let start = reader.seek(SeekFrom::Current(0))?;
+        let (guess, _) = Self::sniff_reader_unseekable(reader)?;
+        reader.seek(SeekFrom::Start(start))?;
+        Ok(guess)
+    }
+
+    /// Guess the MIME type of the content read from `reader` by its signature, as with
+    /// [`sniff_reader()`](#method.sniff_reader), for readers which cannot seek.
+    ///
+    /// The bytes consumed from `reader` (at most [`SNIFF_LEN`](sniff/constant.SNIFF_LEN.html))
+    /// are returned along with the guess so that they can be processed with the rest of the
+    /// content.
+    ///
+    /// Only available with the `sniff` feature.
+    pub fn sniff_reader_unseekable<R: Read>(reader: &mut R) -> io::Result<(MimeGuess, Vec<u8>)> {
+        let mut prefix = Vec::with_capacity(SNIFF_LEN);
+        reader.take(SNIFF_LEN as u64).read_to_end(&mut prefix)?;
+        Ok((Self::sniff_bytes(&prefix), prefix))
+    }
+}
+
+/// Walk the entries of the ZIP archive at the start of `bytes` looking for a more specific
+/// format, stopping at the first entry which is truncated or whose size isn't in its header.
+fn sniff_zip(mut bytes: &[u8]) -> Option<MimeGuess> {
+    fn read_u16(bytes: &[u8], at: usize) -> Option<usize> {
+        let b = bytes.get(at..at + 2)?;
+        Some(usize::from(b[0]) | (usize::from(b[1]) << 8))
+    }
+
+    fn read_u32(bytes: &[u8], at: usize) -> Option<usize> {
+        Some(read_u16(bytes, at)? | (read_u16(bytes, at + 2)? << 16))
+    }
+
+    let mut first = true;
+
+    while bytes.starts_with(ZIP_SIGNATURE) {
+        let size = read_u32(bytes, 18)?;
+        let name_len = read_u16(bytes, 26)?;
+        let extra_len = read_u16(bytes, 28)?;
+
+        // sizes come from untrusted input, so offsets must not overflow on 32-bit targets
+        let name_end = 30usize.checked_add(name_len)?;
+        let data_start = name_end.checked_add(extra_len)?;
+        let data_end = data_start.checked_add(size)?;
+
+        let name = bytes.get(30..name_end)?;
+
+        // OpenDocument and EPUB store their media type uncompressed as the first entry
+        if first && name == b"mimetype" {
+            let mime_str = bytes.get(data_start..data_end)?;
+            let mime_str = ::std::str::from_utf8(mime_str).ok()?;
+            return mime_str
+                .parse()
+                .ok()
+                .map(|mime: ::Mime| ::Guessable::guess(&mime))
+                .filter(|guess| !guess.is_empty());
+        }
+
+        for &(prefix, ext) in ZIP_ENTRY_PREFIXES {
+            if name.starts_with(prefix.as_bytes()) {
+                return Some(MimeGuess::from_ext(ext));
+            }
+        }
+
+        // bit 3: sizes are in a data descriptor after the data, so we can't skip to the next entry
+        if read_u16(bytes, 6)? & 0x8 != 0 {
+            return None;
+        }
+
+        bytes = bytes.get(data_end..)?;
+        first = false;
+    }
+
+    None
+}